@@ -3,13 +3,16 @@
 use std::ops::Deref;
 
 use strum::IntoEnumIterator;
-use web_sys::{HtmlElement, MouseEvent};
-use yew::{AttrValue, Callback, classes, Classes, function_component, html, Html, NodeRef, Properties, ServerRenderer, use_effect, use_effect_with};
+use web_sys::MouseEvent;
+use yew::{AttrValue, Callback, classes, Classes, function_component, html, Html, Properties, use_memo};
 use yew::html::{Children, ImplicitClone, IntoPropValue};
 
-use crate::hooks::{use_sx, use_theme};
-use crate::style::{Color, Variant};
+use crate::hooks::{use_sx_in_layer, use_theme};
+use crate::style_manager::StyleLayer;
+use crate::style::{Color, Size, Variant};
 use crate::theme::sx::Sx;
+use crate::theme::variants::{self, MissingVariantSelectors};
+use crate::theme::Theme;
 
 #[derive(Default, Debug, Clone, PartialEq, Properties)]
 pub struct StylingBoxProps {
@@ -30,52 +33,56 @@ pub struct StylingBoxProps {
     pub class: Classes,
     #[prop_or_default]
     pub disabled: bool,
+    /// The link target, for boxes rendered as `<a>`. Disabled links have no target.
+    #[prop_or_default]
+    pub href: Option<AttrValue>,
+    /// The type of boxes rendered as `<button>`
+    #[prop_or_default]
+    pub r#type: Option<AttrValue>,
     #[prop_or_default]
     pub children: Children,
 }
 
 #[function_component]
 pub fn StylingBox(props: &StylingBoxProps) -> Html {
-    let sx = use_sx(|_, _| props.sx.clone());
+    let sx = use_sx_in_layer(StyleLayer::User, |_, _| props.sx.clone());
     let theme = use_theme();
     let mut classes = classes!(sx);
     classes.extend(props.class.clone());
     classes.extend(classes!(format!("{}-system", theme.prefix)));
 
-
-    let html_ref = yew::use_node_ref();
-    {
-        let html_ref = html_ref.clone();
-        use_effect_with(
-            (props.variant, props.color, props.disabled, html_ref),
-            |(variant, color, disabled, node)| {
-                info!("setting attributes for color and variant for node: {node:?}");
-                let element: HtmlElement = node
-                    .cast::<HtmlElement>()
-                    .expect("should be an html element");
-                if let Some(variant) = **variant {
-                    element
-                        .set_attribute("variant", &variant.to_string())
-                        .expect("could not set variant attribute");
-                }
-                if let Some(color) = **color {
-                    element
-                        .set_attribute("color", &color.to_string())
-                        .expect("could not set color attribute");
-                }
-                if *disabled {
-                    element
-                        .set_attribute("disabled", "")
-                        .expect("could not set color attribute");
-                } else {
-                    let _ = element.remove_attribute("disabled");
-                }
-            },
-        );
-    }
+    // the palette is only validated again when the theme or the color changes
+    let component = props.component.clone();
+    let color = use_memo(
+        (theme.clone(), props.color.clone()),
+        move |(theme, color)| match color.palette(theme) {
+            Ok(color) => color.map(|color| AttrValue::from(color.to_string())),
+            Err(e) => {
+                error!("invalid color for {component}: {e}");
+                None
+            }
+        },
+    );
+    let variant = props.variant.map(|variant| variant.to_string());
+    let disabled = props.disabled.then_some("true");
+    // only form elements can be disabled natively
+    let native_disabled = props.disabled && props.component == "button";
+    // links can't be disabled, so they lose their target and focus instead
+    let href = (!props.disabled).then(|| props.href.clone()).flatten();
+    let tabindex = (props.disabled && props.component == "a").then_some("-1");
 
     html! {
-        <@{props.component.to_string()} onclick={props.onclick.clone()} class={classes} ref={html_ref}>
+        <@{props.component.to_string()}
+            onclick={props.onclick.clone()}
+            class={classes}
+            data-variant={variant}
+            data-color={(*color).clone()}
+            aria-disabled={disabled}
+            disabled={native_disabled}
+            {href}
+            {tabindex}
+            type={props.r#type.clone()}
+        >
             { for props.children.clone() }
         </@>
     }
@@ -112,27 +119,84 @@ impl Deref for VariantProp {
 impl ImplicitClone for VariantProp {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub struct ColorProp(Option<Color>);
+pub struct SizeProp(Option<Size>);
 
-impl IntoPropValue<ColorProp> for &str {
-    fn into_prop_value(self) -> ColorProp {
-        for var in Color::iter() {
-            if var.as_ref().to_lowercase() == self {
-                return ColorProp(Some(var));
+impl IntoPropValue<SizeProp> for &str {
+    fn into_prop_value(self) -> SizeProp {
+        SizeProp(Some(self.into_prop_value()))
+    }
+}
+
+impl IntoPropValue<SizeProp> for Size {
+    fn into_prop_value(self) -> SizeProp {
+        SizeProp(Some(self))
+    }
+}
+
+impl Deref for SizeProp {
+    type Target = Option<Size>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ImplicitClone for SizeProp {}
+
+/// The color of a component, which is the name of a palette of the active theme. Besides the
+/// built-in [`Color`]s, any palette inserted into the theme that defines the variant selectors
+/// can be used.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorProp(Option<AttrValue>);
+
+impl ColorProp {
+    /// Gets the name of the palette, checking that the theme defines it with every variant
+    /// selector.
+    pub fn palette(&self, theme: &Theme) -> Result<Option<&str>, InvalidColor> {
+        let Some(name) = self.0.as_deref() else {
+            return Ok(None);
+        };
+        let palette = theme
+            .get_palette(name)
+            .ok_or_else(|| InvalidColor::UnknownPalette(name.to_string()))?;
+        let missing = variants::missing_selectors(palette);
+        if !missing.is_empty() {
+            return Err(MissingVariantSelectors {
+                palette: name.to_string(),
+                missing,
             }
+            .into());
         }
-        panic!("no Color named {}", self)
+        Ok(Some(name))
     }
 }
 
-impl IntoPropValue<ColorProp> for Color {
+impl IntoPropValue<ColorProp> for &str {
+    fn into_prop_value(self) -> ColorProp {
+        ColorProp(Some(AttrValue::from(self.to_string())))
+    }
+}
+
+impl IntoPropValue<ColorProp> for String {
+    fn into_prop_value(self) -> ColorProp {
+        ColorProp(Some(AttrValue::from(self)))
+    }
+}
+
+impl IntoPropValue<ColorProp> for AttrValue {
     fn into_prop_value(self) -> ColorProp {
         ColorProp(Some(self))
     }
 }
 
+impl IntoPropValue<ColorProp> for Color {
+    fn into_prop_value(self) -> ColorProp {
+        ColorProp(Some(AttrValue::from(self.to_string())))
+    }
+}
+
 impl Deref for ColorProp {
-    type Target = Option<Color>;
+    type Target = Option<AttrValue>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -141,6 +205,15 @@ impl Deref for ColorProp {
 
 impl ImplicitClone for ColorProp {}
 
+/// A component color can't be used with the active theme
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidColor {
+    #[error("no palette named {0:?} in the theme")]
+    UnknownPalette(String),
+    #[error(transparent)]
+    MissingVariantSelectors(#[from] MissingVariantSelectors),
+}
+
 #[cfg(test)]
 mod tests {
     use yew::ServerRenderer;
@@ -153,4 +226,71 @@ mod tests {
         let s = renderer.render().await;
         println!("{s}");
     }
+
+    #[tokio::test]
+    async fn renders_styling_attributes() {
+        let renderer = ServerRenderer::<StylingBox>::with_props(|| StylingBoxProps {
+            variant: Variant::Soft.into_prop_value(),
+            color: Color::Primary.into_prop_value(),
+            disabled: true,
+            ..Default::default()
+        });
+        let s = renderer.render().await;
+        assert!(s.contains(r#"data-variant="soft""#), "{s}");
+        assert!(s.contains(r#"data-color="primary""#), "{s}");
+        assert!(s.contains(r#"aria-disabled="true""#), "{s}");
+        assert!(!s.contains(" disabled"), "{s}");
+
+        let s = ServerRenderer::<StylingBox>::with_props(|| StylingBoxProps {
+            component: "button".into(),
+            disabled: true,
+            ..Default::default()
+        })
+        .render()
+        .await;
+        assert!(s.contains(" disabled"), "{s}");
+
+        let s = ServerRenderer::<StylingBox>::new().render().await;
+        assert!(!s.contains("data-variant"), "{s}");
+        assert!(!s.contains("aria-disabled"), "{s}");
+    }
+
+    #[tokio::test]
+    async fn invalid_colors_render_without_color() {
+        let s = ServerRenderer::<StylingBox>::with_props(|| StylingBoxProps {
+            color: "unknown".into_prop_value(),
+            variant: Variant::Solid.into_prop_value(),
+            ..Default::default()
+        })
+        .render()
+        .await;
+        assert!(s.contains(r#"data-variant="solid""#), "{s}");
+        assert!(!s.contains("data-color"), "{s}");
+    }
+
+    #[test]
+    fn custom_palette_colors() {
+        let mut theme = Theme::default();
+        let primary = theme.get_palette("primary").cloned().unwrap();
+        theme.insert_palette("brand", primary);
+        theme.insert_palette("info", Default::default());
+
+        let name = String::from("brand");
+        let brand: ColorProp = name.as_str().into_prop_value();
+        assert_eq!(brand.palette(&theme).unwrap(), Some("brand"));
+        let success: ColorProp = Color::Success.into_prop_value();
+        assert_eq!(success.palette(&theme).unwrap(), Some("success"));
+        assert_eq!(ColorProp::default().palette(&theme).unwrap(), None);
+
+        let info: ColorProp = "info".into_prop_value();
+        assert!(matches!(
+            info.palette(&theme),
+            Err(InvalidColor::MissingVariantSelectors(_))
+        ));
+        let unknown: ColorProp = "unknown".into_prop_value();
+        assert!(matches!(
+            unknown.palette(&theme),
+            Err(InvalidColor::UnknownPalette(name)) if name == "unknown"
+        ));
+    }
 }
@@ -2,16 +2,20 @@
 
 use std::ops::{Deref, DerefMut};
 use wasm_bindgen::JsCast;
-use yew::{function_component, html, use_effect_with, use_state_eq, Children, Html, Properties};
+use yew::{function_component, html, use_effect_with, use_mut_ref, use_state_eq, AttrValue, Children, Html, Properties};
 
 use crate::theme::baseline::baseline;
 use crate::theme::theme_mode::ThemeMode;
 use crate::theme::{Theme};
 use crate::{Error, hooks, Sx};
-use crate::style_manager::{StyleManager, StyleManagerBackend, StyleManagerBuilder};
+use crate::style_manager::{
+    CascadeLayers, StyleCollector, StyleManager, StyleManagerBackend, StyleManagerBuilder,
+};
 
 pub use self::{mode_context::ThemeModeContext, style_manager_context::StyleManagerContext, theme_context::ThemeContext};
 
+use self::mode_context::{apply_mode, load_mode, store_mode, SystemModeListener};
+
 mod mode_context;
 mod style_manager_context;
 mod theme_context;
@@ -20,6 +24,15 @@ mod theme_context;
 pub struct ThemeProviderProps {
     #[prop_or_default]
     pub theme: Theme,
+    /// Collects all generated css when rendering on the server
+    #[prop_or_default]
+    pub collector: Option<StyleCollector>,
+    /// The names of the cascade layers for the baseline, component styles and user sx
+    #[prop_or_default]
+    pub layers: CascadeLayers,
+    /// The local storage key the chosen mode is persisted under. Set to `None` to not persist it.
+    #[prop_or_else(|| Some("textiler-mode".into()))]
+    pub storage_key: Option<AttrValue>,
     #[prop_or_default]
     pub children: Children,
 }
@@ -32,8 +45,45 @@ pub fn ThemeProvider(props: &ThemeProviderProps) -> Html {
             .prefix(theme_state.prefix.clone())
             .build()
             .expect("could not create style manager")
-    }));
-    let mode = ThemeModeContext::new(use_state_eq(|| ThemeMode::System.detect()));
+    }))
+    .with_collector(props.collector.clone())
+    .with_layers(props.layers.clone());
+    let chosen = use_state_eq(|| {
+        props
+            .storage_key
+            .as_deref()
+            .and_then(load_mode)
+            .unwrap_or_default()
+    });
+    let system = use_state_eq(|| ThemeMode::System.detect());
+    use_effect_with(
+        (props.storage_key.clone(), (*chosen).clone()),
+        |(storage_key, chosen)| {
+            if let Some(storage_key) = storage_key {
+                store_mode(storage_key, chosen);
+            }
+        },
+    );
+    {
+        // only follow the system while it decides the mode. It was just detected on the first
+        // render, but may have changed while another mode was chosen.
+        let system = system.clone();
+        let detected = use_mut_ref(|| true);
+        use_effect_with((*chosen).clone(), move |chosen| {
+            let fresh = detected.replace(false);
+            let listener = (*chosen == ThemeMode::System).then(|| {
+                if !fresh {
+                    system.set(ThemeMode::System.detect());
+                }
+                SystemModeListener::new(move |mode| system.set(mode))
+            });
+            move || {
+                let _listener = listener;
+            }
+        });
+    }
+    use_effect_with((*chosen).clone(), apply_mode);
+    let mode = ThemeModeContext::new(chosen, (*system).clone());
 
     html! {
             <yew::ContextProvider<ThemeContext> context={theme_state}>
@@ -50,13 +100,28 @@ pub fn ThemeProvider(props: &ThemeProviderProps) -> Html {
 pub fn CssBaseline() -> Html {
     let theme = hooks::use_theme();
     let style_manager: StyleManagerContext = hooks::use_style_manager();
-    let (mode, ..) = hooks::use_mode();
 
-    use_effect_with((theme, mode), move |(theme, mode)| {
-        style_manager
-            .mount_main(theme, &mode, baseline(theme, &mode))
-            .expect("could not mount sx");
+    // effects never run on the server, so the baseline is collected while rendering instead
+    if style_manager.collector().is_some() {
+        if let Err(e) = mount_baseline(&style_manager, &theme) {
+            error!("could not collect baseline: {e}");
+        }
+    }
+
+    use_effect_with(theme, move |theme| {
+        if let Err(e) = mount_baseline(&style_manager, theme) {
+            error!("could not mount baseline: {e}");
+        }
     });
 
     html! {}
 }
+
+/// Mounts the baseline of a theme. It contains the variables of both modes, so it's only mounted
+/// once and switching modes doesn't mount it again, but its palette tokens are checked in each
+/// mode.
+fn mount_baseline(style_manager: &StyleManagerContext, theme: &Theme) -> Result<(), Error> {
+    let sx = baseline(theme);
+    let _ = sx.clone().to_css(&ThemeMode::Dark, theme)?;
+    style_manager.mount_main(theme, &ThemeMode::Light, sx)
+}
@@ -2,14 +2,32 @@ use crate::theme::theme_mode::ThemeMode;
 use std::ops::{Deref, DerefMut};
 use yew::UseStateHandle;
 
+/// The mode context, holding the mode chosen by the user and the mode preferred by the system
 #[derive(Debug, Clone, PartialEq)]
 pub struct ThemeModeContext {
     ctx: UseStateHandle<ThemeMode>,
+    system: ThemeMode,
 }
 
 impl ThemeModeContext {
-    pub fn new(ctx: UseStateHandle<ThemeMode>) -> Self {
-        Self { ctx }
+    /// Creates the context from the chosen mode and the mode preferred by the system, which is
+    /// detected once by the provider and then kept up to date by a listener
+    pub fn new(ctx: UseStateHandle<ThemeMode>, system: ThemeMode) -> Self {
+        Self { ctx, system }
+    }
+
+    /// Gets the mode chosen by the user, which may be [`System`](ThemeMode::System)
+    pub fn chosen(&self) -> &ThemeMode {
+        &self.ctx
+    }
+
+    /// Gets the mode in use, which is the system's preferred mode if the chosen mode is
+    /// [`System`](ThemeMode::System)
+    pub fn resolved(&self) -> ThemeMode {
+        match &*self.ctx {
+            ThemeMode::System => self.system.clone(),
+            chosen => chosen.clone(),
+        }
     }
 }
 
@@ -26,3 +44,76 @@ impl DerefMut for ThemeModeContext {
         &mut self.ctx
     }
 }
+
+/// Reads the mode stored under the given key in local storage
+pub(crate) fn load_mode(key: &str) -> Option<ThemeMode> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            use gloo::storage::Storage;
+            gloo::storage::LocalStorage::get(key).ok()
+        } else {
+            let _ = key;
+            None
+        }
+    }
+}
+
+/// Writes the mode to local storage under the given key
+pub(crate) fn store_mode(key: &str, mode: &ThemeMode) {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            use gloo::storage::Storage;
+            if let Err(e) = gloo::storage::LocalStorage::set(key, mode) {
+                error!("could not store theme mode: {e}");
+            }
+        } else {
+            let _ = (key, mode);
+        }
+    }
+}
+
+/// Sets the [`data-mode`](crate::theme::theme_mode::DATA_MODE_ATTRIBUTE) attribute of `<html>`, which switches the mode of the
+/// baseline
+pub(crate) fn apply_mode(mode: &ThemeMode) {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            if let Some(html) = gloo::utils::document().document_element() {
+                if let Err(e) = html.set_attribute(crate::theme::theme_mode::DATA_MODE_ATTRIBUTE, mode.attribute()) {
+                    error!("could not set theme mode: {e:?}");
+                }
+            }
+        } else {
+            let _ = mode;
+        }
+    }
+}
+
+/// Listens for changes of the `prefers-color-scheme` media query until dropped
+pub(crate) struct SystemModeListener {
+    #[cfg(target_arch = "wasm32")]
+    _listener: Option<gloo::events::EventListener>,
+}
+
+impl SystemModeListener {
+    /// Calls `on_change` with the system's preferred mode whenever it changes
+    pub(crate) fn new<F: Fn(ThemeMode) + 'static>(on_change: F) -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                let query = gloo::utils::window()
+                    .match_media("(prefers-color-scheme: dark)")
+                    .ok()
+                    .flatten();
+                Self {
+                    _listener: query.map(|query| {
+                        gloo::events::EventListener::new(&query, "change", move |_| {
+                            on_change(ThemeMode::System.detect())
+                        })
+                    }),
+                }
+            } else {
+                let _ = on_change;
+                Self {}
+            }
+        }
+    }
+}
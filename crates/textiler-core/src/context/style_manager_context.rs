@@ -3,39 +3,127 @@ use std::rc::Rc;
 
 use cfg_if::cfg_if;
 use gloo::utils::document;
-use stylist::style;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlStyleElement, Node};
 
 use crate::{Error, Sx};
-use crate::style_manager::{Css, StyleManager, StyleManagerBackend, SxRef};
+use crate::utils::stable_hash;
+use crate::style_manager::{
+    CascadeLayers, Css, MountedSheet, StyleCollector, StyleLayer, StyleManager, StyleManagerBackend,
+    SxRef,
+};
 use crate::theme::Theme;
 use crate::theme::theme_mode::ThemeMode;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct StyleManagerContext {
     manager: Rc<StyleManagerBackend>,
+    collector: Option<StyleCollector>,
+    layers: CascadeLayers,
 }
 
 impl StyleManagerContext {
     pub fn new(manager: Rc<StyleManagerBackend>) -> Self {
-        Self { manager }
+        Self {
+            manager,
+            collector: None,
+            layers: CascadeLayers::default(),
+        }
     }
 
-    #[cfg(target_arch = "wasm32")]
-    pub(crate) fn mount_wasm(
+    /// Uses the given names for the cascade layers that styles are mounted into
+    pub fn with_layers(self, layers: CascadeLayers) -> Self {
+        Self { layers, ..self }
+    }
+
+    /// Gets the cascade layers that styles are mounted into
+    pub fn layers(&self) -> &CascadeLayers {
+        &self.layers
+    }
+
+    /// Mounts the given [`Css`](Css) into one of the cascade layers
+    pub fn mount_layer(
         &self,
-        theme: &Theme,
-        mode: &ThemeMode,
-        to_mount: Sx,
-    ) -> Result<(), Error> {
+        css: &Css,
+        layer: StyleLayer,
+    ) -> Result<SxRef, <Self as StyleManager>::Error> {
+        self.mount_in_layer(css, self.layers.name(layer))
+    }
+
+    /// Creates the main style sheet, which holds the baseline
+    fn main_css(&self, theme: &Theme, mode: &ThemeMode, to_mount: Sx) -> Result<Css, Error> {
+        let css = to_mount.to_css(mode, theme)?;
+        Ok(self.layers.wrap(StyleLayer::Baseline, &css))
+    }
+
+    fn layers_style_name(&self) -> String {
+        format!("layers-{:x}", stable_hash(self.layers.order()))
+    }
+
+    /// Declares the order of the cascade layers before any layered css is mounted, as layers are
+    /// otherwise ordered by when they're first used. Only the first declaration is kept.
+    fn declare_layers(&self) -> Result<(), Error> {
+        let name = self.layers_style_name();
+        if let Some(collector) = &self.collector {
+            collector.insert_layer_order(name, self.layers.order());
+            return Ok(());
+        }
+        cfg_if! {
+            if #[cfg(target_arch="wasm32")] {
+                self.mount_layer_order(&name)
+            } else {
+                let _ = name;
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn mount_layer_order(&self, name: &str) -> Result<(), Error> {
+        let document = document();
+        let container = document.head().expect("no head");
+        (|| {
+            if container
+                .query_selector(&format!(r#"style[data-style="{name}"]"#))?
+                .is_some()
+            {
+                return Ok(());
+            }
+            let style_element = document.create_element("style")?;
+            style_element.set_attribute("data-style", name)?;
+            style_element.set_text_content(Some(&self.layers.order()));
+            // the order must come before every other layered sheet
+            container.insert_before(&style_element, container.first_child().as_ref())?;
+            Ok(())
+        })()
+        .map_err(|e| Error::Web(Some(e)))
+    }
+
+    /// Records all mounted css into the given collector, for server side rendering
+    pub fn with_collector(self, collector: impl Into<Option<StyleCollector>>) -> Self {
+        Self {
+            collector: collector.into(),
+            ..self
+        }
+    }
+
+    /// Gets the collector used for server side rendering, if present
+    pub fn collector(&self) -> Option<&StyleCollector> {
+        self.collector.as_ref()
+    }
+
+    fn main_style_name(theme: &Theme) -> String {
+        format!("theme-{}-main", theme.prefix)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn mount_wasm(&self, theme: &Theme, css: Css) -> Result<(), Error> {
         let document = document();
         let container = document.head().expect("no head");
 
         (|| {
-            let css = to_mount.to_css(&mode, theme);
             let style_element = document.create_element("style")?;
-            let theme_name = format!("theme-{}-main", theme.prefix);
+            let theme_name = Self::main_style_name(theme);
             style_element.set_attribute("data-style", &theme_name)?;
             let base_css = css.to_string();
 
@@ -69,7 +157,14 @@ impl StyleManagerContext {
             if let Some(ref existing) = existing {
                 container.replace_child(&style_element, existing)?;
             } else {
-                container.append_child(&style_element)?;
+                // right after the layer order, so other sheets can override the baseline
+                let layers = container
+                    .query_selector(&format!(r#"style[data-style="{}"]"#, self.layers_style_name()))?;
+                let next = match layers {
+                    Some(layers) => layers.next_sibling(),
+                    None => container.first_child(),
+                };
+                container.insert_before(&style_element, next.as_ref())?;
             }
 
             Ok(())
@@ -77,9 +172,15 @@ impl StyleManagerContext {
         .map_err(|e| Error::Web(Some(e)))
     }
     pub fn mount_main(&self, theme: &Theme, mode: &ThemeMode, to_mount: Sx) -> Result<(), crate::Error> {
+        let css = self.main_css(theme, mode, to_mount)?;
+        self.declare_layers()?;
+        if let Some(collector) = &self.collector {
+            collector.insert_main(Self::main_style_name(theme), css);
+            return Ok(());
+        }
         cfg_if! {
             if #[cfg(target_arch="wasm32")] {
-                self.mount_wasm(theme, mode, to_mount)
+                self.mount_wasm(theme, css)
             } else {
                 Err(Error::MountingUnsupported)
             }
@@ -103,12 +204,28 @@ impl StyleManager for StyleManagerContext {
         panic!("can not build")
     }
 
+    /// Mounts the given [`Css`](Css) into the components layer
     fn mount(&self, css: &Css) -> Result<SxRef, Self::Error> {
+        self.mount_layer(css, StyleLayer::Components)
+    }
+
+    fn mount_in_layer(&self, css: &Css, layer: &str) -> Result<SxRef, Self::Error> {
         if css.trim().is_empty() {
-            Ok(SxRef::new(style!().unwrap()))
+            Ok(SxRef::empty())
         } else {
-            self.manager.mount(css)
+            if let Err(e) = self.declare_layers() {
+                error!("could not declare cascade layers: {e}");
+            }
+            let sx_ref = self.manager.mount_in_layer(css, layer)?;
+            if let Some(collector) = &self.collector {
+                collector.insert_sheet(sx_ref.class_name(), sx_ref.css().to_string());
+            }
+            Ok(sx_ref)
         }
 
     }
+
+    fn mounted_sheets(&self) -> Vec<MountedSheet> {
+        self.manager.mounted_sheets()
+    }
 }
@@ -10,8 +10,8 @@ pub enum Error {
     Web(Option<JsValue>),
     #[error("Mounting not supported on this architecture")]
     MountingUnsupported,
-    #[error("an error occurred while trying to convert sx to css")]
-    SxToCssError,
+    #[error(transparent)]
+    SxToCssError(#[from] crate::theme::sx::SxToCssError),
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
@@ -6,5 +6,5 @@ mod use_theme;
 
 pub use use_mode::use_mode;
 pub(crate) use use_style_manager::use_style_manager;
-pub use use_sx::use_sx;
+pub use use_sx::{use_sx, use_sx_in_layer};
 pub use use_theme::use_theme;
@@ -2,6 +2,8 @@ use crate::context::ThemeModeContext;
 use crate::theme::theme_mode::ThemeMode;
 use yew::{hook, use_context, Callback};
 
+/// Gets the mode in use and a callback to choose a mode. If [`System`](ThemeMode::System) is chosen,
+/// the mode follows the system's preference.
 #[hook]
 pub fn use_mode() -> (ThemeMode, Callback<ThemeMode>) {
     let ctx = use_context::<ThemeModeContext>();
@@ -11,7 +13,7 @@ pub fn use_mode() -> (ThemeMode, Callback<ThemeMode>) {
                 let ctx = ctx.clone();
                 Callback::from(move |mode| ctx.set(mode))
             };
-            ((**ctx).clone(), callback)
+            (ctx.resolved(), callback)
         }
         None => (ThemeMode::System, Callback::from(|_| {})),
     }
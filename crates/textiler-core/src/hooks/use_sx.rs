@@ -4,14 +4,25 @@ use crate::context::StyleManagerContext;
 
 use crate::hooks::{use_mode, use_style_manager, use_theme};
 use crate::theme::ThemeMode;
-use crate::style_manager::{SxRef, StyleManager};
+use crate::style_manager::{Css, StyleLayer, SxRef};
 use crate::theme::sx::Sx;
 use crate::theme::Theme;
 
 
-/// Use sx attaches sx to the css body
+/// Use sx attaches sx to the css body, in the components layer
 #[hook]
 pub fn use_sx<F>(source: F) -> SxRef
+where
+    F : Fn(&Theme, &ThemeMode) -> Sx
+{
+    use_sx_in_layer(StyleLayer::Components, source)
+}
+
+/// Attaches sx to the css body in the given cascade layer. The sheet is shared with every other
+/// component using the same css, and is unmounted along with the last of them. Sx given by users should use
+/// [`StyleLayer::User`](StyleLayer::User), so that it always overrides component styles.
+#[hook]
+pub fn use_sx_in_layer<F>(layer: StyleLayer, source: F) -> SxRef
 where
     F : Fn(&Theme, &ThemeMode) -> Sx
 {
@@ -26,8 +37,16 @@ where
             "creating css fron sx:{sx:#?} using theme {} with mode {mode:?}",
             theme.prefix
         );
-        sx.clone().to_css(mode, theme)
+        sx.clone().to_css(mode, theme).unwrap_or_else(|e| {
+            error!("could not convert sx to css: {e}");
+            Css::new()
+        })
     });
 
-    manager.mount(&*css).expect("could not mount css")
+    // the mounted sheet is kept alive by the memo, so it's unmounted once this component unmounts
+    // or the css changes, unless other components still use it
+    let sx_ref = use_memo((css, layer, manager), |(css, layer, manager)| {
+        manager.mount_layer(css, *layer).expect("could not mount css")
+    });
+    (*sx_ref).clone()
 }
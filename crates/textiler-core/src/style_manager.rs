@@ -10,17 +10,28 @@ use cfg_if::cfg_if;
 use static_assertions::assert_impl_all;
 use yew::Classes;
 use crate::Sx;
+use crate::utils::stable_hash;
 
 
-#[cfg(feature = "stylist")]
+mod collector;
+mod registry;
+mod scope;
+pub use collector::StyleCollector;
+
+#[cfg(feature = "native")]
+pub mod native;
+// native is used whenever it's enabled, as features are additive and stylist is a default feature
+#[cfg(all(feature = "stylist", not(feature = "native")))]
 pub mod stylist;
-#[cfg(feature = "stylist")]
-pub use stylist as platform;
 
 
 cfg_if!(
-    if #[cfg(not(any(feature="stylist")))] {
-        compile_error!("most select a backend. Backends are [stylist]");
+    if #[cfg(feature = "native")] {
+        pub use native as platform;
+    } else if #[cfg(all(feature = "stylist", not(feature = "native")))] {
+        pub use stylist as platform;
+    } else {
+        compile_error!("most select a backend. Backends are [native, stylist]");
     }
 );
 
@@ -29,6 +40,12 @@ assert_impl_all!(SxRef: Into<Classes>);
 
 pub type Css = String;
 
+/// Creates the class name for a sheet from the theme prefix and a stable hash of its css, so the
+/// same css gets the same class name across renders, on the server and on the client.
+pub(crate) fn class_name(prefix: &str, css: &str) -> String {
+    format!("{prefix}-{:x}", stable_hash(css))
+}
+
 /// A style manager mounts [`Sx`](Sx) instances onto web pages for consumption of styled components
 pub trait StyleManager {
     type Builder;
@@ -39,6 +56,105 @@ pub trait StyleManager {
 
     /// Mounts the given [`Css`](Css) onto the web page
     fn mount(&self, css: &Css) -> Result<SxRef, Self::Error>;
+
+    /// Mounts the given [`Css`](Css) onto the web page, wrapped in the named cascade layer
+    fn mount_in_layer(&self, css: &Css, layer: &str) -> Result<SxRef, Self::Error>;
+
+    /// Lists the currently mounted sheets, for debugging. Identical css is only mounted once, and
+    /// a sheet is unmounted once every [`SxRef`](SxRef) to it is dropped.
+    fn mounted_sheets(&self) -> Vec<MountedSheet>;
+}
+
+/// A sheet mounted by a [`StyleManager`](StyleManager)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountedSheet {
+    class_name: String,
+    css: Css,
+    references: usize,
+}
+
+impl MountedSheet {
+    /// Gets the class name of the sheet
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Gets the css of the sheet, scoped to its class name
+    pub fn css(&self) -> &str {
+        &self.css
+    }
+
+    /// Gets the number of [`SxRef`](SxRef)s using the sheet
+    pub fn references(&self) -> usize {
+        self.references
+    }
+}
+
+/// The cascade layers styles are mounted into. Layers are ordered from lowest to highest
+/// priority, so user sx always wins over component styles, which always win over the baseline,
+/// regardless of selector specificity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StyleLayer {
+    /// The baseline emitted by [`CssBaseline`](crate::prelude::CssBaseline)
+    Baseline,
+    /// The styles of components, mounted with [`use_sx`](crate::hooks::use_sx)
+    #[default]
+    Components,
+    /// The `sx` given to components by users
+    User,
+}
+
+/// The names of the cascade layers, which default to `textiler.baseline`, `textiler.components`
+/// and `textiler.user`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CascadeLayers {
+    baseline: String,
+    components: String,
+    user: String,
+}
+
+impl CascadeLayers {
+    /// Creates cascade layers with the given names
+    pub fn new(
+        baseline: impl AsRef<str>,
+        components: impl AsRef<str>,
+        user: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            baseline: baseline.as_ref().to_string(),
+            components: components.as_ref().to_string(),
+            user: user.as_ref().to_string(),
+        }
+    }
+
+    /// Gets the name of a layer
+    pub fn name(&self, layer: StyleLayer) -> &str {
+        match layer {
+            StyleLayer::Baseline => &self.baseline,
+            StyleLayer::Components => &self.components,
+            StyleLayer::User => &self.user,
+        }
+    }
+
+    /// Gets the `@layer` statement declaring the order of the layers. It must come before any
+    /// other layered css for the order to apply.
+    pub fn order(&self) -> Css {
+        format!(
+            "@layer {}, {}, {};",
+            self.baseline, self.components, self.user
+        )
+    }
+
+    /// Wraps css in the given layer
+    pub fn wrap(&self, layer: StyleLayer, css: &str) -> Css {
+        format!("@layer {} {{{css}}}", self.name(layer))
+    }
+}
+
+impl Default for CascadeLayers {
+    fn default() -> Self {
+        Self::new("textiler.baseline", "textiler.components", "textiler.user")
+    }
 }
 
 /// Builds a style manager
@@ -0,0 +1,261 @@
+//! Collects generated css while rendering on the server.
+
+use std::sync::{Arc, Mutex};
+
+use indexmap::IndexMap;
+
+use crate::style_manager::Css;
+
+/// Collects the baseline and every mounted sheet while rendering, so that server rendered pages
+/// can inline their styles into `<head>`.
+///
+/// A collector is given to a [`ThemeProvider`](crate::context::ThemeProvider) through its
+/// `collector` prop. After rendering, [`style_tags`](StyleCollector::style_tags) returns the styles
+/// as html. [`ThemeMode::init_script`](crate::theme::ThemeMode::init_script) should be inlined
+/// before them, so the stored mode is applied before the page is painted.
+///
+/// Collecting is always available and isn't behind a cargo feature, only rendering on the server
+/// needs yew's `ssr` feature to be enabled by the application.
+///
+/// ```no_run
+/// # use textiler_core::prelude::*;
+/// # use textiler_core::style_manager::StyleCollector;
+/// # use yew::{function_component, html, Html, Properties, ServerRenderer};
+/// #[derive(PartialEq, Properties)]
+/// struct AppProps {
+///     collector: StyleCollector,
+/// }
+///
+/// #[function_component]
+/// fn App(props: &AppProps) -> Html {
+///     html! {
+///         <ThemeProvider collector={props.collector.clone()}>
+///             <CssBaseline />
+///             <Sheet>{"Hello, world"}</Sheet>
+///         </ThemeProvider>
+///     }
+/// }
+///
+/// # async fn render() {
+/// let collector = StyleCollector::new();
+/// let body = ServerRenderer::<App>::with_props({
+///     let collector = collector.clone();
+///     move || AppProps { collector }
+/// })
+/// .render()
+/// .await;
+/// // sets the stored mode before the page is painted, so the page doesn't flash the wrong mode
+/// let script = ThemeMode::init_script("textiler-mode");
+/// let head = format!("<script>{script}</script>{}", collector.style_tags());
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleCollector {
+    inner: Arc<Mutex<CollectedStyles>>,
+}
+
+#[derive(Debug, Default)]
+struct CollectedStyles {
+    layers: IndexMap<String, Css>,
+    main: IndexMap<String, Css>,
+    sheets: IndexMap<String, Css>,
+}
+
+impl StyleCollector {
+    /// Creates a new, empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the statement declaring the order of cascade layers, unless already recorded
+    pub(crate) fn insert_layer_order(&self, name: impl AsRef<str>, css: Css) {
+        let _ = self
+            .lock()
+            .layers
+            .entry(name.as_ref().to_string())
+            .or_insert(css);
+    }
+
+    /// Records the main style sheet of a theme, replacing any previously recorded one
+    pub(crate) fn insert_main(&self, name: impl AsRef<str>, css: Css) {
+        let _ = self.lock().main.insert(name.as_ref().to_string(), css);
+    }
+
+    /// Records a sheet mounted under the given id
+    pub(crate) fn insert_sheet(&self, id: impl AsRef<str>, css: Css) {
+        let _ = self.lock().sheets.insert(id.as_ref().to_string(), css);
+    }
+
+    /// Gets all collected css, with the layer order and main style sheets first
+    pub fn css(&self) -> Css {
+        let styles = self.lock();
+        styles
+            .layers
+            .values()
+            .chain(styles.main.values())
+            .chain(styles.sheets.values())
+            .map(|css| css.as_str())
+            .collect()
+    }
+
+    /// Gets all collected css as `<style>` elements, using the same `data-style` ids that are
+    /// used when mounting on the client.
+    pub fn style_tags(&self) -> String {
+        let styles = self.lock();
+        styles
+            .layers
+            .iter()
+            .chain(styles.main.iter())
+            .chain(styles.sheets.iter())
+            .map(|(id, css)| {
+                format!(
+                    r#"<style data-style="{id}">{css}</style>"#,
+                    css = css.replace("</", r"<\/")
+                )
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CollectedStyles> {
+        self.inner.lock().expect("style collector is poisoned")
+    }
+}
+
+impl PartialEq for StyleCollector {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yew::{function_component, html, Html, Properties, ServerRenderer};
+
+    use crate::prelude::*;
+    use crate::style_manager::{CascadeLayers, StyleCollector};
+
+    #[derive(PartialEq, Properties)]
+    struct AppProps {
+        collector: StyleCollector,
+        #[prop_or_default]
+        layers: CascadeLayers,
+    }
+
+    #[function_component]
+    fn App(props: &AppProps) -> Html {
+        html! {
+            <ThemeProvider collector={props.collector.clone()} layers={props.layers.clone()}>
+                <CssBaseline />
+                <Sheet sx={sx!{ "padding": "12px" }}>{"Hello, world"}</Sheet>
+            </ThemeProvider>
+        }
+    }
+
+    #[tokio::test]
+    async fn collects_baseline_and_sheets() {
+        let collector = StyleCollector::new();
+        let rendered = ServerRenderer::<App>::with_props({
+            let collector = collector.clone();
+            move || AppProps {
+                collector,
+                layers: CascadeLayers::default(),
+            }
+        })
+        .render()
+        .await;
+
+        let css = collector.css();
+        assert!(css.contains("--textiler-palette-background-body"), "baseline missing: {css}");
+        assert!(css.contains("padding: 12px"), "sheet missing: {css}");
+        assert!(
+            css.starts_with("@layer textiler.baseline, textiler.components, textiler.user;"),
+            "layer order must come first: {css}"
+        );
+        assert!(css.contains("@layer textiler.baseline {"), "{css}");
+        assert!(css.contains("@layer textiler.user {"), "{css}");
+
+        let tags = collector.style_tags();
+        assert!(tags.contains(r#"<style data-style="theme-textiler-main">"#));
+        let class = tags
+            .split(r#"data-style=""#)
+            .last()
+            .and_then(|s| s.split('"').next())
+            .expect("should have a sheet");
+        assert!(rendered.contains(class), "{class} not in {rendered}");
+    }
+
+    #[tokio::test]
+    async fn uses_custom_layer_names() {
+        let collector = StyleCollector::new();
+        let _ = ServerRenderer::<App>::with_props({
+            let collector = collector.clone();
+            move || AppProps {
+                collector,
+                layers: CascadeLayers::new("base", "lib", "app"),
+            }
+        })
+        .render()
+        .await;
+
+        let css = collector.css();
+        assert!(css.starts_with("@layer base, lib, app;"), "{css}");
+        assert!(css.contains("@layer base {"), "{css}");
+        assert!(css.contains("@layer app {"), "{css}");
+        assert!(!css.contains("textiler.user"), "{css}");
+    }
+
+    #[tokio::test]
+    async fn declares_layer_order_without_baseline() {
+        #[derive(PartialEq, Properties)]
+        struct NoBaselineProps {
+            collector: StyleCollector,
+        }
+
+        #[function_component]
+        fn NoBaseline(props: &NoBaselineProps) -> Html {
+            html! {
+                <ThemeProvider collector={props.collector.clone()}>
+                    <Sheet sx={sx!{ "padding": "12px" }}>{"Hello, world"}</Sheet>
+                </ThemeProvider>
+            }
+        }
+
+        let collector = StyleCollector::new();
+        let _ = ServerRenderer::<NoBaseline>::with_props({
+            let collector = collector.clone();
+            move || NoBaselineProps { collector }
+        })
+        .render()
+        .await;
+
+        let css = collector.css();
+        assert!(!css.contains("--textiler-palette-background-body"), "{css}");
+        assert!(
+            css.starts_with("@layer textiler.baseline, textiler.components, textiler.user;"),
+            "layer order must come first: {css}"
+        );
+        assert_eq!(css.matches("@layer textiler.baseline,").count(), 1, "{css}");
+        assert!(collector.style_tags().starts_with(r#"<style data-style="layers-"#));
+    }
+
+    #[tokio::test]
+    async fn class_names_are_deterministic() {
+        let render = || async {
+            let collector = StyleCollector::new();
+            let rendered = ServerRenderer::<App>::with_props({
+                let collector = collector.clone();
+                move || AppProps {
+                    collector,
+                    layers: CascadeLayers::default(),
+                }
+            })
+            .render()
+            .await;
+            (rendered, collector.style_tags())
+        };
+        let (first, first_tags) = render().await;
+        let (second, second_tags) = render().await;
+        assert_eq!(first, second);
+        assert_eq!(first_tags, second_tags);
+    }
+}
@@ -0,0 +1,226 @@
+//! A style manager backend without any styling dependencies.
+//!
+//! Class names are derived from a hash of the css, so the same css always gets the same class.
+//! Sheets are injected into `<head>` as `<style>` elements on wasm, and are only recorded in memory
+//! on other targets.
+
+use std::rc::Rc;
+
+use yew::Classes;
+
+use crate::style_manager::registry::{Mounted, SheetRegistry};
+use crate::style_manager::scope::scope;
+use crate::style_manager::{class_name, Css, MountedSheet, StyleManagerBuilder};
+use crate::Error;
+
+use super::StyleManager as StyleManagerTrait;
+
+/// A style manager that scopes css to hashed class names by itself
+#[derive(Debug, Clone)]
+pub struct Manager {
+    prefix: String,
+    sheets: SheetRegistry<StyleElement>,
+}
+
+impl Manager {
+    fn new(prefix: String) -> Self {
+        Self {
+            prefix,
+            sheets: Default::default(),
+        }
+    }
+
+    fn mount_source(&self, source: Css) -> Result<SxRef, Error> {
+        let class_name = class_name(&self.prefix, &source);
+        if let Some(sheet) = self.sheets.get(&class_name) {
+            return Ok(SxRef::new(sheet));
+        }
+        let css = scope(&source, &class_name);
+        #[cfg(target_arch = "wasm32")]
+        inject(&class_name, &css)?;
+        let element = StyleElement {
+            id: class_name.clone(),
+        };
+        Ok(SxRef::new(self.sheets.insert(class_name, css, element)))
+    }
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Self::new("textiler".to_string())
+    }
+}
+
+impl PartialEq for Manager {
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix && self.sheets.ptr_eq(&other.sheets)
+    }
+}
+
+impl StyleManagerTrait for Manager {
+    type Builder = Builder;
+    type Error = Error;
+
+    fn builder() -> Self::Builder {
+        Builder {
+            prefix: "textiler".to_string(),
+        }
+    }
+
+    fn mount(&self, css: &Css) -> Result<SxRef, Self::Error> {
+        self.mount_source(css.clone())
+    }
+
+    fn mount_in_layer(&self, css: &Css, layer: &str) -> Result<SxRef, Self::Error> {
+        self.mount_source(format!("@layer {layer} {{{css}}}"))
+    }
+
+    fn mounted_sheets(&self) -> Vec<MountedSheet> {
+        self.sheets.sheets()
+    }
+}
+
+pub struct Builder {
+    prefix: String,
+}
+
+impl StyleManagerBuilder for Builder {
+    type Built = Manager;
+    type Error = Error;
+
+    fn prefix<S: AsRef<str>>(self, prefix: S) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+        }
+    }
+
+    fn build(self) -> Result<Self::Built, Self::Error> {
+        Ok(Manager::new(self.prefix))
+    }
+}
+
+/// A style ref can be used as a css class. The sheet stays mounted while any clone of its style
+/// ref exists.
+#[derive(Debug, Clone)]
+pub struct SxRef {
+    sheet: Option<Rc<Mounted<StyleElement>>>,
+}
+
+impl SxRef {
+    fn new(sheet: Rc<Mounted<StyleElement>>) -> Self {
+        Self { sheet: Some(sheet) }
+    }
+
+    /// A style ref without any css
+    pub(crate) fn empty() -> Self {
+        Self { sheet: None }
+    }
+
+    /// Gets the class name of the mounted sheet
+    pub fn class_name(&self) -> &str {
+        self.sheet
+            .as_ref()
+            .map(|sheet| sheet.class_name())
+            .unwrap_or_default()
+    }
+
+    /// Gets the css of the mounted sheet, scoped to its class name
+    pub fn css(&self) -> &str {
+        self.sheet
+            .as_ref()
+            .map(|sheet| sheet.css())
+            .unwrap_or_default()
+    }
+}
+
+impl PartialEq for SxRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.class_name() == other.class_name()
+    }
+}
+
+impl From<SxRef> for Classes {
+    fn from(value: SxRef) -> Self {
+        match value.sheet {
+            Some(sheet) => Classes::from(sheet.class_name().to_string()),
+            None => Classes::new(),
+        }
+    }
+}
+
+/// The `<style>` element of a mounted sheet, which is removed when dropped
+struct StyleElement {
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    id: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for StyleElement {
+    fn drop(&mut self) {
+        let selector = format!(r#"style[data-style="{}"]"#, self.id);
+        if let Ok(Some(element)) = gloo::utils::document().query_selector(&selector) {
+            element.remove();
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn inject(id: &str, css: &str) -> Result<(), Error> {
+    let document = gloo::utils::document();
+    let head = document.head().expect("no head");
+    (|| {
+        // sheets rendered on the server are already present
+        if head
+            .query_selector(&format!(r#"style[data-style="{id}"]"#))?
+            .is_some()
+        {
+            return Ok(());
+        }
+        let style_element = document.create_element("style")?;
+        style_element.set_attribute("data-style", id)?;
+        style_element.set_text_content(Some(css));
+        head.append_child(&style_element)?;
+        Ok(())
+    })()
+    .map_err(|e| Error::Web(Some(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_css_same_class() {
+        let manager = Manager::builder()
+            .prefix("app")
+            .build()
+            .expect("could not build manager");
+        let first = manager
+            .mount_in_layer(&"color: red;".to_string(), "user")
+            .expect("could not mount");
+        let second = manager
+            .mount_in_layer(&"color: red;".to_string(), "user")
+            .expect("could not mount");
+        assert_eq!(first, second);
+        assert!(first.class_name().starts_with("app-"));
+        assert_eq!(
+            first.css(),
+            format!("@layer user {{.{} {{color: red;}}}}", first.class_name())
+        );
+        assert_eq!(manager.mounted_sheets().len(), 1);
+        assert_eq!(manager.mounted_sheets()[0].references(), 2);
+    }
+
+    #[test]
+    fn unmounts_with_last_ref() {
+        let manager = Manager::default();
+        let first = manager
+            .mount(&"color: red;".to_string())
+            .expect("could not mount");
+        let clone = first.clone();
+        drop(first);
+        assert_eq!(manager.mounted_sheets().len(), 1);
+        drop(clone);
+        assert!(manager.mounted_sheets().is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! Reference counts mounted sheets, so identical css is only mounted once and a sheet is unmounted
+//! once its last user is dropped.
+
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::rc::{Rc, Weak};
+
+use indexmap::IndexMap;
+
+use crate::style_manager::{Css, MountedSheet};
+
+type Sheets<H> = RefCell<IndexMap<String, Weak<Mounted<H>>>>;
+
+/// The sheets mounted by a style manager, by class name. `H` is the backend's handle to a mounted
+/// sheet, which unmounts the sheet when dropped.
+pub(crate) struct SheetRegistry<H> {
+    sheets: Rc<Sheets<H>>,
+}
+
+impl<H> SheetRegistry<H> {
+    /// Gets a mounted sheet by its class name
+    pub(crate) fn get(&self, class_name: &str) -> Option<Rc<Mounted<H>>> {
+        self.sheets
+            .borrow()
+            .get(class_name)
+            .and_then(|sheet| sheet.upgrade())
+    }
+
+    /// Records a newly mounted sheet
+    pub(crate) fn insert(&self, class_name: String, css: Css, handle: H) -> Rc<Mounted<H>> {
+        let mounted = Rc::new(Mounted {
+            class_name: class_name.clone(),
+            css,
+            _handle: handle,
+            sheets: Rc::downgrade(&self.sheets),
+        });
+        let _ = self
+            .sheets
+            .borrow_mut()
+            .insert(class_name, Rc::downgrade(&mounted));
+        mounted
+    }
+
+    /// Lists the currently mounted sheets, in the order they were mounted
+    pub(crate) fn sheets(&self) -> Vec<MountedSheet> {
+        self.sheets
+            .borrow()
+            .values()
+            .filter_map(|sheet| {
+                let references = sheet.strong_count();
+                sheet.upgrade().map(|sheet| MountedSheet {
+                    class_name: sheet.class_name.clone(),
+                    css: sheet.css.clone(),
+                    references,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.sheets, &other.sheets)
+    }
+}
+
+impl<H> Default for SheetRegistry<H> {
+    fn default() -> Self {
+        Self {
+            sheets: Default::default(),
+        }
+    }
+}
+
+impl<H> Clone for SheetRegistry<H> {
+    fn clone(&self) -> Self {
+        Self {
+            sheets: self.sheets.clone(),
+        }
+    }
+}
+
+impl<H> Debug for SheetRegistry<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.sheets.borrow().keys()).finish()
+    }
+}
+
+/// A mounted sheet, which is removed from its registry and unmounted when dropped
+pub(crate) struct Mounted<H> {
+    class_name: String,
+    css: Css,
+    _handle: H,
+    sheets: Weak<Sheets<H>>,
+}
+
+impl<H> Mounted<H> {
+    pub(crate) fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    pub(crate) fn css(&self) -> &str {
+        &self.css
+    }
+}
+
+impl<H> Debug for Mounted<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mounted")
+            .field("class_name", &self.class_name)
+            .field("css", &self.css)
+            .finish()
+    }
+}
+
+impl<H> Drop for Mounted<H> {
+    fn drop(&mut self) {
+        if let Some(sheets) = self.sheets.upgrade() {
+            let mut sheets = sheets.borrow_mut();
+            // the sheet may have been mounted again after this one was released
+            if sheets
+                .get(&self.class_name)
+                .is_some_and(|sheet| sheet.strong_count() == 0)
+            {
+                let _ = sheets.shift_remove(&self.class_name);
+            }
+        }
+    }
+}
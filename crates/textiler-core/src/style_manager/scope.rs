@@ -0,0 +1,193 @@
+//! Scopes css to a class name, the way stylist does for its own class names.
+
+use crate::style_manager::Css;
+
+/// Scopes css to a class name, following the same rules as stylist:
+///
+/// - declarations outside any selector apply to the class itself
+/// - selectors containing `&` have it replaced by the class
+/// - selectors starting with a pseudo class or element apply to the class itself
+/// - other selectors apply to descendants of the class
+/// - at-rules are kept, with their contents scoped, except for `@keyframes`
+pub(crate) fn scope(css: &str, class_name: &str) -> Css {
+    let mut scoped = Css::new();
+    scope_into(css, &format!(".{class_name}"), &mut scoped);
+    scoped
+}
+
+fn scope_into(css: &str, class: &str, out: &mut Css) {
+    let mut declarations = vec![];
+    let mut blocks = Css::new();
+    for item in items(css) {
+        match item {
+            Item::Declaration(declaration) => declarations.push(declaration),
+            Item::Block { prelude, body } if prelude.starts_with("@keyframes") => {
+                blocks.push_str(&format!("{prelude} {{{body}}}"));
+            }
+            Item::Block { prelude, body } if prelude.starts_with('@') => {
+                blocks.push_str(&format!("{prelude} {{"));
+                scope_into(body, class, &mut blocks);
+                blocks.push('}');
+            }
+            Item::Block { prelude, body } => {
+                let selectors = split_top_level(prelude, ',')
+                    .into_iter()
+                    .map(|selector| scope_selector(selector.trim(), class))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                blocks.push_str(&format!("{selectors} {{{}}}", body.trim()));
+            }
+        }
+    }
+    if !declarations.is_empty() {
+        out.push_str(&format!("{class} {{"));
+        for declaration in declarations {
+            out.push_str(&format!("{declaration};"));
+        }
+        out.push('}');
+    }
+    out.push_str(&blocks);
+}
+
+fn scope_selector(selector: &str, class: &str) -> String {
+    if selector.contains('&') || selector.contains(":root") {
+        selector.replace('&', class).replace(":root", class)
+    } else if selector.starts_with(':') {
+        format!("{class}{selector}")
+    } else {
+        format!("{class} {selector}")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Item<'a> {
+    Declaration(&'a str),
+    Block { prelude: &'a str, body: &'a str },
+}
+
+/// Splits css into its top level declarations and blocks
+fn items(css: &str) -> Vec<Item<'_>> {
+    let mut items = vec![];
+    let mut scanner = Scanner::default();
+    let mut start = 0;
+    let mut open = 0;
+    for (index, c) in css.char_indices() {
+        if !scanner.advance(c) {
+            continue;
+        }
+        match c {
+            ';' if scanner.depth == 0 && scanner.parens == 0 => {
+                let declaration = css[start..index].trim();
+                if !declaration.is_empty() {
+                    items.push(Item::Declaration(declaration));
+                }
+                start = index + 1;
+            }
+            '{' => {
+                if scanner.depth == 0 {
+                    open = index;
+                }
+                scanner.depth += 1;
+            }
+            '}' if scanner.depth > 0 => {
+                scanner.depth -= 1;
+                if scanner.depth == 0 {
+                    items.push(Item::Block {
+                        prelude: css[start..open].trim(),
+                        body: &css[open + 1..index],
+                    });
+                    start = index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    let rest = css[start..].trim();
+    if !rest.is_empty() && scanner.depth == 0 {
+        items.push(Item::Declaration(rest));
+    }
+    items
+}
+
+/// Splits on a separator that isn't within parentheses or quotes, like the commas of a selector list
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut scanner = Scanner::default();
+    let mut start = 0;
+    for (index, c) in value.char_indices() {
+        if scanner.advance(c) && c == separator && scanner.parens == 0 {
+            parts.push(&value[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Tracks quotes, escapes and parentheses while scanning css
+#[derive(Default)]
+struct Scanner {
+    quote: Option<char>,
+    escaped: bool,
+    parens: usize,
+    depth: usize,
+}
+
+impl Scanner {
+    /// Advances past a character, returning whether it's outside of any string
+    fn advance(&mut self, c: char) -> bool {
+        if self.escaped {
+            self.escaped = false;
+            return false;
+        }
+        match (self.quote, c) {
+            (_, '\\') => {
+                self.escaped = true;
+                false
+            }
+            (Some(quote), c) => {
+                if quote == c {
+                    self.quote = None;
+                }
+                false
+            }
+            (None, '"' | '\'') => {
+                self.quote = Some(c);
+                false
+            }
+            (None, '(') => {
+                self.parens += 1;
+                true
+            }
+            (None, ')') => {
+                self.parens = self.parens.saturating_sub(1);
+                true
+            }
+            (None, _) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_to_class() {
+        let css = scope(
+            r#"padding: 1px;:hover {color: red;}.icon, &:focus {color: blue;}@media (min-width: 768px) {padding: 3px;::before {content: "{";}}@keyframes fade {from {opacity: 0;}}"#,
+            "textiler-1",
+        );
+        assert_eq!(
+            css,
+            concat!(
+                ".textiler-1 {padding: 1px;}",
+                ".textiler-1:hover {color: red;}",
+                ".textiler-1 .icon, .textiler-1:focus {color: blue;}",
+                "@media (min-width: 768px) {.textiler-1 {padding: 3px;}",
+                r#".textiler-1::before {content: "{";}}"#,
+                "@keyframes fade {from {opacity: 0;}}",
+            )
+        );
+    }
+}
@@ -1,15 +1,26 @@
 use std::borrow::Cow;
+use std::rc::Rc;
+use std::str::FromStr;
 
+use stylist::ast::{Bow, Rule, RuleBlockContent, ScopeContent, Sheet, StringFragment};
 use stylist::manager::StyleManager;
-use stylist::Style;
+use stylist::GlobalStyle;
 use yew::Classes;
 
-use crate::style_manager::{Css, StyleManagerBuilder};
+use crate::style_manager::scope::scope;
+use crate::style_manager::registry::{Mounted, SheetRegistry};
+use crate::style_manager::{class_name, Css, MountedSheet, StyleManagerBuilder};
 
 use super::StyleManager as StyleManagerTrait;
 
-#[derive(Debug, Clone, PartialEq, Default)]
-pub struct Manager(StyleManager);
+#[derive(Debug, Clone, Default)]
+pub struct Manager(StyleManager, SheetRegistry<Registered>);
+
+impl PartialEq for Manager {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1.ptr_eq(&other.1)
+    }
+}
 
 impl StyleManagerTrait for Manager {
     type Builder = Builder;
@@ -20,8 +31,55 @@ impl StyleManagerTrait for Manager {
     }
 
     fn mount(&self, css: &Css) -> Result<SxRef, Self::Error> {
-        Style::new_with_manager(&**css, &self.0)
-            .map(|style| SxRef::new(style))
+        self.mount_source(css, None)
+    }
+
+    fn mount_in_layer(&self, css: &Css, layer: &str) -> Result<SxRef, Self::Error> {
+        self.mount_source(css, Some(layer))
+    }
+
+    fn mounted_sheets(&self) -> Vec<MountedSheet> {
+        self.1.sheets()
+    }
+}
+
+impl Manager {
+    /// Mounts css scoped to a class name hashed from its content. The scoped css is mounted as a
+    /// global style, so stylist doesn't pick a random class name of its own.
+    fn mount_source(&self, css: &Css, layer: Option<&str>) -> Result<SxRef, stylist::Error> {
+        let source = match layer {
+            Some(layer) => format!("@layer {layer} {{{css}}}"),
+            None => css.clone(),
+        };
+        let class_name = class_name(&self.0.prefix(), &source);
+        if let Some(sheet) = self.1.get(&class_name) {
+            return Ok(SxRef::new(sheet));
+        }
+        let sheet = Sheet::from_str(&scope(css, &class_name))?;
+        let sheet = match layer {
+            // stylist can't parse @layer, so the parsed sheet is wrapped in a layer rule instead
+            Some(layer) => {
+                let content = sheet
+                    .iter()
+                    .map(|scope| match scope {
+                        ScopeContent::Block(block) => {
+                            RuleBlockContent::Block(Bow::Boxed(Box::new(block.clone())))
+                        }
+                        ScopeContent::Rule(rule) => {
+                            RuleBlockContent::Rule(Bow::Boxed(Box::new(rule.clone())))
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Sheet::from(vec![ScopeContent::Rule(Rule {
+                    condition: vec![StringFragment::from(format!("@layer {layer}"))].into(),
+                    content: content.into(),
+                })])
+            }
+            None => sheet,
+        };
+        let style = GlobalStyle::new_with_manager(sheet, &self.0)?;
+        let css = style.get_style_str().to_string();
+        Ok(SxRef::new(self.1.insert(class_name, css, Registered(style))))
     }
 }
 
@@ -36,25 +94,90 @@ impl StyleManagerBuilder for Builder {
     }
 
     fn build(self) -> Result<Self::Built, Self::Error> {
-        Ok(Manager(self.0.build()?))
+        Ok(Manager(self.0.build()?, Default::default()))
     }
 }
 
-/// A style ref can be used as a css class
+/// A style ref can be used as a css class. The sheet stays mounted while any clone of its style
+/// ref exists.
 #[derive(Debug, Clone)]
 pub struct SxRef {
-    style: Style,
+    sheet: Option<Rc<Mounted<Registered>>>,
 }
 
 impl SxRef {
-    pub(crate) fn new(style: Style) -> Self {
-        Self { style }
+    fn new(sheet: Rc<Mounted<Registered>>) -> Self {
+        Self { sheet: Some(sheet) }
+    }
+
+    /// A style ref without any css
+    pub(crate) fn empty() -> Self {
+        Self { sheet: None }
+    }
+
+    /// Gets the class name of the mounted sheet
+    pub fn class_name(&self) -> &str {
+        self.sheet
+            .as_ref()
+            .map(|sheet| sheet.class_name())
+            .unwrap_or_default()
+    }
+
+    /// Gets the css of the mounted sheet, scoped to its class name
+    pub fn css(&self) -> &str {
+        self.sheet
+            .as_ref()
+            .map(|sheet| sheet.css())
+            .unwrap_or_default()
+    }
+}
+
+impl PartialEq for SxRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.class_name() == other.class_name()
     }
 }
 
 impl From<SxRef> for Classes {
     fn from(value: SxRef) -> Self {
-        Classes::from(value.style)
+        match value.sheet {
+            Some(sheet) => Classes::from(sheet.class_name().to_string()),
+            None => Classes::new(),
+        }
+    }
+}
+
+/// A style registered with stylist, which is unregistered when dropped so stylist unmounts it
+struct Registered(GlobalStyle);
+
+impl Drop for Registered {
+    fn drop(&mut self) {
+        self.0.unregister();
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedups_and_unmounts_sheets() {
+        let manager = Manager::builder()
+            .prefix("app")
+            .build()
+            .expect("could not build manager");
+        let css = "color: red;".to_string();
+        let first = manager.mount(&css).expect("could not mount");
+        let second = manager.mount(&css).expect("could not mount");
+        assert_eq!(first, second);
+
+        let sheets = manager.mounted_sheets();
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(sheets[0].references(), 2);
+        assert!(sheets[0].css().contains(first.class_name()));
+
+        drop(first);
+        drop(second);
+        assert!(manager.mounted_sheets().is_empty());
+    }
+}
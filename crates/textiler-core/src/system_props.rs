@@ -2,92 +2,264 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
 use once_cell::sync::Lazy;
 
 use crate::theme::breakpoint::Breakpoints;
+use crate::theme::Theme;
+use crate::utils::to_property;
 
-/// Contains standard system properties and their translations, should only exist as a
-/// singleton instance [`SYSTEM_PROPERTIES`](SYSTEM_PROPERTIES).
-#[derive(Debug, Clone)]
+/// A registry of system properties and their translations. Every [`Theme`](Theme) has its own
+/// registry, starting with the default shorthands in [`SYSTEM_PROPERTIES`](SYSTEM_PROPERTIES).
+#[derive(Clone)]
 pub struct SystemProperties {
     mappings: HashMap<String, Vec<String>>,
+    translators: Vec<Arc<dyn CssPropertyTranslator + Send + Sync>>,
 }
 
 static SYSTEM_PROPS_MAP: &[(&str, &[&str])] = &[
+    // margin
+    ("m", &["margin"]),
+    ("mt", &["marginTop"]),
+    ("mr", &["marginRight"]),
+    ("mb", &["marginBottom"]),
+    ("ml", &["marginLeft"]),
+    ("mx", &["marginLeft", "marginRight"]),
+    ("my", &["marginTop", "marginBottom"]),
+    ("marginX", &["margin-left", "margin-right"]),
+    ("marginY", &["margin-top", "margin-bottom"]),
+    // padding
     ("p", &["padding"]),
     ("pl", &["paddingLeft"]),
     ("pr", &["paddingRight"]),
     ("pt", &["paddingTop"]),
     ("pb", &["paddingBottom"]),
+    ("px", &["paddingLeft", "paddingRight"]),
+    ("py", &["paddingTop", "paddingBottom"]),
     ("pX", &["paddingLeft", "paddingRight"]),
     ("pY", &["paddingTop", "paddingBottom"]),
+    ("paddingX", &["paddingLeft", "paddingRight"]),
+    ("paddingY", &["paddingTop", "paddingBottom"]),
+    // sizing
+    ("w", &["width"]),
+    ("h", &["height"]),
+    ("minW", &["minWidth"]),
+    ("maxW", &["maxWidth"]),
+    ("minH", &["minHeight"]),
+    ("maxH", &["maxHeight"]),
+    ("boxSize", &["width", "height"]),
+    // flex
+    ("flexDir", &["flexDirection"]),
+    ("justify", &["justifyContent"]),
+    ("align", &["alignItems"]),
+    ("grow", &["flexGrow"]),
+    ("shrink", &["flexShrink"]),
+    // grid
+    ("gridCols", &["gridTemplateColumns"]),
+    ("gridRows", &["gridTemplateRows"]),
+    ("colGap", &["columnGap"]),
+    // position
+    ("pos", &["position"]),
+    ("insetX", &["left", "right"]),
+    ("insetY", &["top", "bottom"]),
+    // border
+    ("rounded", &["borderRadius"]),
+    ("borderX", &["borderLeft", "borderRight"]),
+    ("borderY", &["borderTop", "borderBottom"]),
+    // background
     ("bgcolor", &["backgroundColor"]),
     ("bg", &["background"]),
-    ("marginX", &["margin-left", "margin-right"]),
-    ("marginY", &["margin-top", "margin-bottom"]),
+    // pseudo classes and elements
+    ("_hover", &["&:hover"]),
+    ("_focus", &["&:focus"]),
+    ("_focusVisible", &["&:focus-visible"]),
+    ("_focusWithin", &["&:focus-within"]),
+    ("_active", &["&:active"]),
+    ("_visited", &["&:visited"]),
+    ("_disabled", &["&:disabled", "&[aria-disabled=true]"]),
+    ("_checked", &["&:checked", "&[aria-checked=true]"]),
+    ("_selected", &["&[aria-selected=true]"]),
+    ("_invalid", &["&:invalid", "&[aria-invalid=true]"]),
+    ("_first", &["&:first-child"]),
+    ("_last", &["&:last-child"]),
+    ("_before", &["&::before"]),
+    ("_after", &["&::after"]),
+    ("_placeholder", &["&::placeholder"]),
 ];
 
 impl SystemProperties {
-    /// Create a new system properties instance
-    fn new() -> Self {
-
+    /// Creates a system properties registry without any properties
+    pub fn new() -> Self {
         Self {
-            mappings:
-            SYSTEM_PROPS_MAP
-            .iter()
-            .map(|(k, v): &(&str, &[&str])| {
-                (
-                    k.to_string(),
-                    v.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
-                )
-            })
-            .collect(),
+            mappings: HashMap::new(),
+            translators: vec![],
+        }
+    }
+
+    /// Adds a shorthand, which translates to the given css properties. Replaces any previous
+    /// translation of the shorthand.
+    pub fn insert<I, S>(&mut self, shorthand: &str, properties: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let properties = properties
+            .into_iter()
+            .map(|s| s.as_ref().to_string())
+            .collect::<Vec<_>>();
+        let normalized = to_property(shorthand);
+        if normalized != shorthand && !shorthand.starts_with('_') {
+            // sx keys may be written in camel or kebab case, so shorthands are found in either form.
+            // Pseudo shorthands like `_hover` would lose their underscore, so they're skipped.
+            let _ = self.mappings.insert(normalized, properties.clone());
         }
+        let _ = self.mappings.insert(shorthand.to_string(), properties);
+    }
+
+    /// Removes a shorthand
+    pub fn remove(&mut self, shorthand: &str) {
+        let _ = self.mappings.remove(shorthand);
+        let _ = self.mappings.remove(&to_property(shorthand));
+    }
+
+    /// Checks if a shorthand is registered
+    pub fn contains(&self, shorthand: &str) -> bool {
+        self.mappings.contains_key(shorthand)
+    }
+
+    /// Registers a custom translator, which is used for properties that aren't a registered
+    /// shorthand. Translators are tried in the order they're registered, and the first translator
+    /// that changes a property is used.
+    pub fn register<T: CssPropertyTranslator + Send + Sync + 'static>(&mut self, translator: T) {
+        self.translators.push(Arc::new(translator));
+    }
+
+    /// Creates a new registry with the shorthands and translators of `overrides` added to this
+    /// registry, with the shorthands of `overrides` winning on conflicts
+    pub fn extend(&self, overrides: &SystemProperties) -> SystemProperties {
+        let mut props = self.clone();
+        for (shorthand, properties) in &overrides.mappings {
+            let _ = props
+                .mappings
+                .insert(shorthand.clone(), properties.clone());
+        }
+        for translator in &overrides.translators {
+            if !props.translators.iter().any(|t| Arc::ptr_eq(t, translator)) {
+                props.translators.push(translator.clone());
+            }
+        }
+        props
+    }
+}
+
+impl Default for SystemProperties {
+    /// The default shorthands for margin, padding, sizing, flex, grid, position, border, background
+    /// and pseudo selectors
+    fn default() -> Self {
+        let mut props = Self::new();
+        for (shorthand, properties) in SYSTEM_PROPS_MAP {
+            props.insert(shorthand, *properties);
+        }
+        props
+    }
+}
+
+impl Debug for SystemProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SystemProperties")
+            .field("mappings", &self.mappings)
+            .field("translators", &self.translators.len())
+            .finish()
+    }
+}
+
+impl PartialEq for SystemProperties {
+    fn eq(&self, other: &Self) -> bool {
+        self.mappings == other.mappings
+            && self.translators.len() == other.translators.len()
+            && self
+                .translators
+                .iter()
+                .zip(&other.translators)
+                .all(|(l, r)| Arc::ptr_eq(l, r))
     }
 }
 
 impl CssPropertyTranslator for SystemProperties {
     fn translate<'a>(&self, query: &'a str) -> Vec<Cow<'a, str>> {
-        self.mappings
-            .get(query)
-            .map(|result| {
-                result
-                    .iter()
-                    .map(|s| Cow::<str>::Owned(s.clone()))
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_else(move || vec![Cow::Borrowed(query)])
+        if let Some(result) = self.mappings.get(query) {
+            return result
+                .iter()
+                .map(|s| Cow::<str>::Owned(s.clone()))
+                .collect::<Vec<_>>();
+        }
+        for translator in &self.translators {
+            let translated = translator.translate(query);
+            if !matches!(&translated[..], [only] if only == query) {
+                return translated;
+            }
+        }
+        vec![Cow::Borrowed(query)]
     }
 }
 
-pub static SYSTEM_PROPERTIES: Lazy<SystemProperties> = Lazy::new(|| SystemProperties::new());
+/// The default system properties, used when inserting keys into an [`Sx`](crate::Sx)
+pub static SYSTEM_PROPERTIES: Lazy<SystemProperties> = Lazy::new(SystemProperties::default);
+
+/// Checks if a css property accepts spacing, which lets numeric sx values be multiples of the
+/// theme's spacing unit.
+pub(crate) fn is_spacing_property(property: &str) -> bool {
+    property.starts_with("margin")
+        || property.starts_with("padding")
+        || property == "gap"
+        || property.ends_with("-gap")
+}
 
 /// attempts to translate a given css query into a modified one
 #[derive(Debug)]
-pub struct TranslationUnit {
-    props: SystemProperties,
-    bps: Breakpoints,
+pub struct TranslationUnit<'a> {
+    props: &'a SystemProperties,
+    bps: &'a Breakpoints,
 }
 
-impl TranslationUnit {
-    pub fn new(bps: &Breakpoints) -> Self {
+impl<'a> TranslationUnit<'a> {
+    /// Creates a translation unit borrowing the system properties and breakpoints of a theme
+    pub fn new(theme: &'a Theme) -> Self {
         Self {
-            props: SYSTEM_PROPERTIES.clone(),
-            bps: bps.clone(),
+            props: theme.system_properties(),
+            bps: theme.breakpoints(),
         }
     }
 }
 
-impl CssPropertyTranslator for TranslationUnit {
+impl TranslationUnit<'_> {
+    /// Translates a container breakpoint key, like `@md` for the nearest container or `@md/card`
+    /// for the container named `card`
+    fn container_query(&self, query: &str) -> Option<String> {
+        let key = query.strip_prefix('@')?;
+        let (key, container) = match key.split_once('/') {
+            None => (key, None),
+            Some((key, container)) => (key, Some(container)),
+        };
+        self.bps.container_query(key, container)
+    }
+}
+
+impl CssPropertyTranslator for TranslationUnit<'_> {
     fn translate<'a>(&self, query: &'a str) -> Vec<Cow<'a, str>> {
-        if self.props.mappings.contains_key(query) {
-            self.props.translate(query)
-        } else if let Some(breakpoint) = self.bps.get(query) {
-            vec![Cow::Owned(format!(
-                "@media (min-width: {}px)",
-                breakpoint.width()
-            ))]
+        let translated = self.props.translate(query);
+        if !matches!(&translated[..], [only] if only == query) {
+            translated
+        } else if query.starts_with('@') {
+            // other at-rules, like @media, @supports or @container, are used as written
+            match self.container_query(query) {
+                Some(container_query) => vec![Cow::Owned(container_query)],
+                None => vec![Cow::Borrowed(query)],
+            }
+        } else if let Some(media_query) = self.bps.query(query) {
+            vec![Cow::Owned(media_query)]
         } else {
             vec![Cow::Borrowed(query)]
         }
@@ -99,3 +271,48 @@ pub trait CssPropertyTranslator {
     /// Translates
     fn translate<'a>(&self, query: &'a str) -> Vec<Cow<'a, str>>;
 }
+
+impl<F> CssPropertyTranslator for F
+where
+    F: Fn(&str) -> Option<Vec<String>>,
+{
+    fn translate<'a>(&self, query: &'a str) -> Vec<Cow<'a, str>> {
+        match self(query) {
+            Some(translated) => translated.into_iter().map(Cow::Owned).collect(),
+            None => vec![Cow::Borrowed(query)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_shorthands() {
+        let props = SystemProperties::default();
+        assert_eq!(props.translate("mt"), vec!["marginTop"]);
+        assert_eq!(props.translate("px"), vec!["paddingLeft", "paddingRight"]);
+        assert_eq!(props.translate("minW"), vec!["minWidth"]);
+        assert_eq!(props.translate("min-w"), vec!["minWidth"]);
+        assert_eq!(props.translate("color"), vec!["color"]);
+        assert_eq!(
+            props.translate("_disabled"),
+            vec!["&:disabled", "&[aria-disabled=true]"]
+        );
+    }
+
+    #[test]
+    fn custom_translators() {
+        let mut props = SystemProperties::default();
+        props.insert("sz", ["width", "height"]);
+        props.register(|query: &str| {
+            query
+                .strip_prefix("brand-")
+                .map(|rest| vec![format!("--brand-{rest}")])
+        });
+        assert_eq!(props.translate("sz"), vec!["width", "height"]);
+        assert_eq!(props.translate("brand-accent"), vec!["--brand-accent"]);
+        assert_eq!(props.translate("p"), vec!["padding"]);
+    }
+}
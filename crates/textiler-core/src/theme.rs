@@ -3,6 +3,8 @@
 use std::collections::HashMap;
 use std::ops::Deref;
 
+use bigdecimal::BigDecimal;
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use yew::Properties;
 
@@ -10,20 +12,27 @@ use crate::theme::breakpoint::Breakpoints;
 pub use color::Color;
 use regex::Regex;
 
+use crate::theme::keyframes::Keyframes;
 use crate::theme::palette::Palette;
+use crate::theme::scale::Scale;
+use crate::system_props::SystemProperties;
 use crate::theme::typography::{TypographyLevel, TypographyScale};
 use crate::utils::to_property;
 
 pub mod color;
+pub mod contrast;
 
 pub mod baseline;
 pub mod breakpoint;
 pub mod gradient;
+pub mod keyframes;
 pub mod palette;
 pub mod parsing;
+pub mod scale;
 pub mod sx;
 pub mod theme_mode;
 pub mod typography;
+pub mod variants;
 
 pub use theme_mode::ThemeMode;
 
@@ -33,6 +42,12 @@ pub struct Theme {
     breakpoints: Breakpoints,
     palettes: HashMap<String, Palette>,
     typography: TypographyScale,
+    spacing: String,
+    radius: Scale<String>,
+    shadow: Scale<String>,
+    z_index: Scale<i32>,
+    system_properties: SystemProperties,
+    animations: Scale<Keyframes>,
 }
 
 static DEFAULT_THEME: Lazy<Theme> = Lazy::new(|| {
@@ -56,6 +71,12 @@ impl Theme {
             breakpoints: Default::default(),
             palettes: Default::default(),
             typography: Default::default(),
+            spacing: "8px".to_string(),
+            radius: Default::default(),
+            shadow: Default::default(),
+            z_index: Default::default(),
+            system_properties: Default::default(),
+            animations: Default::default(),
         }
     }
 
@@ -69,20 +90,252 @@ impl Theme {
         self.palettes.get_mut(name)
     }
 
-    /// Insert a palette into the theme
+    /// Insert a palette into the theme. Palettes used as component colors aren't checked for
+    /// their variant selectors here, see [`validate_variants`](Self::validate_variants).
     pub fn insert_palette(&mut self, name: impl AsRef<str>, palette: Palette) {
         let _ = self.palettes.insert(name.as_ref().to_string(), palette);
     }
 
+    /// Checks that every palette named after a [`Color`](crate::style::Color) defines the
+    /// selectors used to style each variant
+    pub fn validate_variants(&self) -> Result<(), variants::MissingVariantSelectors> {
+        variants::validate(self)
+    }
+
     /// Creates a new palette if not yet present, and returns a mutable reference to it.
     pub fn palette(&mut self, name: impl AsRef<str>) -> &mut Palette {
         self.palettes.entry(name.as_ref().to_string()).or_default()
     }
 
+    /// Creates a new theme with `overrides` layered on top of this theme. Only what the overrides
+    /// set is changed, and where both set something the overrides win:
+    ///
+    /// - if the overrides set a prefix, palette variables of this theme are renamed to match it
+    /// - breakpoints are set by name
+    /// - palettes are merged per selector, with a selector in `overrides` replacing the colors
+    ///   for every mode
+    /// - typography levels are merged per property with [`Sx::extend`](sx::Sx::extend)
+    /// - the spacing unit is replaced, and radius, shadow and z-index scales are merged by name
+    /// - system properties are merged by shorthand, and animations by name
+    pub fn extend(&self, overrides: &ThemeOverrides) -> Theme {
+        let mut theme = match &overrides.prefix {
+            Some(prefix) => self.with_renamed_prefix(prefix),
+            None => self.clone(),
+        };
+        for (abbrev, width) in &overrides.breakpoints {
+            theme.breakpoints.set(abbrev, *width);
+        }
+        for (name, palette) in &overrides.palettes {
+            let extended = match theme.palettes.get(name) {
+                Some(base) => base.extend(palette),
+                None => palette.clone(),
+            };
+            theme.insert_palette(name, extended);
+        }
+        theme.typography = theme.typography.extend(&overrides.typography);
+        if let Some(spacing) = &overrides.spacing {
+            theme.spacing = spacing.clone();
+        }
+        theme.radius = theme.radius.extend(&overrides.radius);
+        theme.shadow = theme.shadow.extend(&overrides.shadow);
+        theme.z_index = theme.z_index.extend(&overrides.z_index);
+        theme.system_properties = theme
+            .system_properties
+            .extend(&overrides.system_properties);
+        theme.animations = theme.animations.extend(&overrides.animations);
+        theme
+    }
+
+    /// Creates a copy of this theme using a different prefix, renaming the palette variables that
+    /// refer to this theme's palettes.
+    pub(crate) fn with_renamed_prefix(&self, prefix: &str) -> Theme {
+        if self.prefix == prefix {
+            return self.clone();
+        }
+        let renamed = Theme::with_prefix(prefix);
+        let palettes = self
+            .palettes
+            .iter()
+            .map(|(name, palette)| {
+                (
+                    name.clone(),
+                    palette.map_colors(|color| self.rename_palette_vars(color, &renamed)),
+                )
+            })
+            .collect();
+        Theme {
+            prefix: renamed.prefix,
+            palettes,
+            ..self.clone()
+        }
+    }
+
+    fn rename_palette_vars(&self, color: &Color, target: &Theme) -> Color {
+        match color {
+            Color::Var { var, fallback } => Color::Var {
+                var: self
+                    .find_palette_var(var)
+                    .map(|(palette, selector)| target.palette_var(palette, selector))
+                    .unwrap_or_else(|| var.clone()),
+                fallback: fallback
+                    .as_ref()
+                    .map(|fallback| Box::new(self.rename_palette_vars(fallback, target))),
+            },
+            color => color.clone(),
+        }
+    }
+
+    /// Selects a color from a palette for the given mode, following variables that reference other
+    /// palettes until a concrete color is found. Unresolvable variables use their fallback.
+    pub fn resolve_color(&self, palette: &str, selector: &str, mode: &ThemeMode) -> Option<Color> {
+        let color = self.get_palette(palette)?.select(selector, mode)?;
+        self.resolve_var(color, mode, 0)
+    }
+
+    fn resolve_var(&self, color: &Color, mode: &ThemeMode, depth: usize) -> Option<Color> {
+        const MAX_DEPTH: usize = 16;
+        let Color::Var { var, fallback } = color else {
+            return Some(color.clone());
+        };
+        let referenced = if depth < MAX_DEPTH {
+            match PALETTE_SELECTOR_REGEX.captures(var) {
+                Some(captures) => self
+                    .get_palette(&captures["palette"])
+                    .and_then(|palette| palette.select(&captures["selector"], mode)),
+                None => self
+                    .find_palette_var(var)
+                    .and_then(|(palette, selector)| self.get_palette(palette)?.select(selector, mode)),
+            }
+            .and_then(|referenced| self.resolve_var(referenced, mode, depth + 1))
+        } else {
+            None
+        };
+        referenced.or_else(|| {
+            fallback
+                .as_ref()
+                .and_then(|fallback| self.resolve_var(fallback, mode, depth + 1))
+        })
+    }
+
+    /// Finds the palette and selector a css variable created by [`palette_var`](Self::palette_var)
+    /// refers to
+    pub fn find_palette_var(&self, var: &str) -> Option<(&str, &str)> {
+        self.palettes().find_map(|(palette_name, palette)| {
+            palette
+                .selectors()
+                .find(|selector| self.palette_var(palette_name, selector) == var)
+                .map(|selector| (palette_name, selector))
+        })
+    }
+
     pub fn palette_var(&self, palette: &str, selector: &str) -> String {
         to_property(format!("--{}-palette-{palette}-{selector}", self.prefix))
     }
 
+    /// Gets the css variable of a value in one of the theme's scales, such as `radius`
+    pub fn scale_var(&self, scale: &str, name: &str) -> String {
+        to_property(format!("--{}-{scale}-{name}", self.prefix))
+    }
+
+    /// Resolves a `scale.name` token, like `radius.md` or `shadow.lg`, into the css variable
+    /// holding its value, or an `animation.name` token into its keyframes name. Returns `None` if
+    /// the scale or value doesn't exist.
+    pub fn scale_token(&self, scale: &str, name: &str) -> Option<String> {
+        let exists = match scale {
+            "radius" => self.radius.get(name).is_some(),
+            "shadow" => self.shadow.get(name).is_some(),
+            "zIndex" => self.z_index.get(name).is_some(),
+            "animation" => {
+                return self
+                    .animations
+                    .get(name)
+                    .map(|_| self.animation_name(name))
+            }
+            _ => return None,
+        };
+        exists.then(|| format!("var({})", self.scale_var(scale, name)))
+    }
+
+    /// Gets the css variable containing the spacing unit
+    pub fn spacing_var(&self) -> String {
+        to_property(format!("--{}-spacing", self.prefix))
+    }
+
+    /// Gets the css for a multiple of the spacing unit, so `theme.spacing(2)` is twice the unit.
+    pub fn spacing<N: Into<BigDecimal>>(&self, factor: N) -> String {
+        format!("calc({} * var({}))", factor.into(), self.spacing_var())
+    }
+
+    /// Gets the spacing unit, such as `8px`
+    pub fn spacing_unit(&self) -> &str {
+        &self.spacing
+    }
+
+    /// Sets the spacing unit
+    pub fn set_spacing_unit(&mut self, unit: impl AsRef<str>) {
+        self.spacing = unit.as_ref().to_string();
+    }
+
+    /// Gets the border radius scale
+    pub fn radius(&self) -> &Scale<String> {
+        &self.radius
+    }
+
+    /// Gets a mutable reference to the border radius scale
+    pub fn radius_mut(&mut self) -> &mut Scale<String> {
+        &mut self.radius
+    }
+
+    /// Gets the box shadow scale
+    pub fn shadow(&self) -> &Scale<String> {
+        &self.shadow
+    }
+
+    /// Gets a mutable reference to the box shadow scale
+    pub fn shadow_mut(&mut self) -> &mut Scale<String> {
+        &mut self.shadow
+    }
+
+    /// Gets the z-index layers
+    pub fn z_index(&self) -> &Scale<i32> {
+        &self.z_index
+    }
+
+    /// Gets a mutable reference to the z-index layers
+    pub fn z_index_mut(&mut self) -> &mut Scale<i32> {
+        &mut self.z_index
+    }
+
+    /// Gets the keyframes of a named animation, referenced in sx with tokens like `animation.spin`
+    pub fn animation(&self, name: &str) -> Option<&Keyframes> {
+        self.animations.get(name)
+    }
+
+    /// Gets the name of the `@keyframes` rule emitted for a named animation
+    pub fn animation_name(&self, name: &str) -> String {
+        to_property(format!("{}-{name}", self.prefix))
+    }
+
+    /// Gets all named animations
+    pub fn animations(&self) -> &Scale<Keyframes> {
+        &self.animations
+    }
+
+    /// Gets a mutable reference to the named animations
+    pub fn animations_mut(&mut self) -> &mut Scale<Keyframes> {
+        &mut self.animations
+    }
+
+    /// Gets the system properties used to translate sx shorthands for this theme
+    pub fn system_properties(&self) -> &SystemProperties {
+        &self.system_properties
+    }
+
+    /// Gets a mutable reference to the system properties, to add shorthands or translators
+    pub fn system_properties_mut(&mut self) -> &mut SystemProperties {
+        &mut self.system_properties
+    }
+
     pub fn class_var(&self, class: &str, var_name: &str) -> String {
         to_property(format!("--{}-{class}-{var_name}", self.prefix))
     }
@@ -117,7 +370,104 @@ impl Theme {
     }
 }
 
+/// The parts of a theme changed by [`Theme::extend`]. Anything left unset keeps the value of the
+/// extended theme.
+#[derive(Debug, Clone)]
+pub struct ThemeOverrides {
+    /// Replaces the prefix, renaming the palette variables of the extended theme
+    pub prefix: Option<String>,
+    /// Breakpoint widths by name
+    pub breakpoints: IndexMap<String, u16>,
+    pub palettes: HashMap<String, Palette>,
+    pub typography: TypographyScale,
+    /// Replaces the spacing unit
+    pub spacing: Option<String>,
+    pub radius: Scale<String>,
+    pub shadow: Scale<String>,
+    pub z_index: Scale<i32>,
+    /// Shorthands and translators added to the system properties of the extended theme
+    pub system_properties: SystemProperties,
+    pub animations: Scale<Keyframes>,
+}
+
+impl Default for ThemeOverrides {
+    fn default() -> Self {
+        Self {
+            prefix: None,
+            breakpoints: Default::default(),
+            palettes: Default::default(),
+            typography: Default::default(),
+            spacing: None,
+            radius: Default::default(),
+            shadow: Default::default(),
+            z_index: Default::default(),
+            system_properties: SystemProperties::new(),
+            animations: Default::default(),
+        }
+    }
+}
+
 pub static PALETTE_SELECTOR_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"^(?<palette>[a-zA-Z_]\w*)\.(?<selector>\w+)$"#)
         .expect("could not create palette selector")
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_keeps_what_overrides_leave_unset() {
+        let mut base = Theme::with_prefix("brand");
+        *base.breakpoints_mut() = Breakpoints::from_iter([("sm", 0), ("wide", 1400)]);
+        base.system_properties_mut().remove("mx");
+
+        let extended = base.extend(&ThemeOverrides {
+            breakpoints: IndexMap::from_iter([("tablet".to_string(), 900)]),
+            ..Default::default()
+        });
+
+        assert_eq!(extended.prefix, "brand");
+        let breakpoints = extended
+            .breakpoints()
+            .points()
+            .into_iter()
+            .map(|bp| (bp.abbrev().to_string(), bp.width()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            breakpoints,
+            [
+                ("sm".to_string(), 0),
+                ("tablet".to_string(), 900),
+                ("wide".to_string(), 1400)
+            ]
+        );
+        assert!(!extended.system_properties().contains("mx"));
+    }
+
+    #[test]
+    fn extend_keeps_spacing_unless_set() {
+        let mut base = Theme::new();
+        base.set_spacing_unit("4px");
+
+        let kept = base.extend(&ThemeOverrides::default());
+        assert_eq!(kept.spacing_unit(), "4px");
+
+        let replaced = base.extend(&ThemeOverrides {
+            spacing: Some("0.5rem".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(replaced.spacing_unit(), "0.5rem");
+    }
+
+    #[test]
+    fn extend_renames_prefix_if_set() {
+        let base = Theme::default();
+        let extended = base.extend(&ThemeOverrides {
+            prefix: Some("brand".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(extended.prefix, "brand");
+        assert_eq!(extended.palette_var("primary", "500"), "--brand-palette-primary-500");
+    }
+}
@@ -1,11 +1,19 @@
+use strum::IntoEnumIterator;
+
+use crate::style::Color;
 use crate::theme::sx::SxValue;
-use crate::theme::theme_mode::ThemeMode;
+use crate::theme::theme_mode::{ThemeMode, DATA_MODE_ATTRIBUTE};
 use crate::theme::typography::TypographyLevel;
+use crate::theme::variants;
 use crate::theme::Theme;
 use crate::{sx, Sx};
 
-/// Creates the base style sheet for happiness
-pub fn baseline(theme: &Theme, mode: &ThemeMode) -> Sx {
+/// Creates the base style sheet for happiness. The palette variables of both modes are emitted at
+/// once, so switching modes only changes the `data-mode` attribute of `<html>`:
+///
+/// - `light` and `dark` force a mode
+/// - any other value, like `system`, follows the `prefers-color-scheme` media query
+pub fn baseline(theme: &Theme) -> Sx {
     let mut emit = sx!();
 
     let theme_system_class = theme.system_class();
@@ -16,21 +24,41 @@ pub fn baseline(theme: &Theme, mode: &ThemeMode) -> Sx {
         }
     }
 
-    for (palette_name, palette) in theme.palettes() {
-        let mut to_merge = sx!();
-        for selector_name in palette.selectors() {
-            let mut selector = palette.select(selector_name, mode).unwrap().clone();
-            if let Ok(adjusted) = selector.to_rgba_color() {
-                selector = adjusted;
+    let light = palette_vars(theme, &ThemeMode::Light);
+    let dark = palette_vars(theme, &ThemeMode::Dark);
+    let dark_selector = format!("&[{DATA_MODE_ATTRIBUTE}=dark]");
+    let system_dark_selector = format!("&:not([{DATA_MODE_ATTRIBUTE}=light])");
+    emit = emit.merge(sx! {
+        "html": light.merge(sx! {
+            (dark_selector): dark.clone(),
+        }),
+        "@media (prefers-color-scheme: dark)": {
+            "html": {
+                (system_dark_selector): dark,
             }
-            to_merge.insert(
-                theme.palette_var(palette_name, selector_name),
-                SxValue::Color(selector),
-            )
         }
-        emit = emit.merge(sx! {
-            "html": to_merge
-        })
+    });
+
+    let mut scales = sx!();
+    scales.insert(theme.spacing_var(), SxValue::CssLiteral(theme.spacing_unit().to_string()));
+    for (name, radius) in theme.radius().iter() {
+        scales.insert(theme.scale_var("radius", name), SxValue::CssLiteral(radius.clone()));
+    }
+    for (name, shadow) in theme.shadow().iter() {
+        scales.insert(theme.scale_var("shadow", name), SxValue::CssLiteral(shadow.clone()));
+    }
+    for (name, z_index) in theme.z_index().iter() {
+        scales.insert(theme.scale_var("zIndex", name), SxValue::from(*z_index));
+    }
+    emit = emit.merge(sx! {
+        "html": scales
+    });
+
+    for (name, keyframes) in theme.animations().iter() {
+        emit.insert(
+            format!("@keyframes {}", theme.animation_name(name)),
+            keyframes.frames().clone(),
+        );
     }
 
     emit.merge(sx! {
@@ -48,25 +76,72 @@ pub fn baseline(theme: &Theme, mode: &ThemeMode) -> Sx {
             "margin": "0",
         },
         (theme.system_class()): {
-            "&[color=success]": {
-                "color": "success.050",
-            },
-            "&[variant=outlined]": {
+            "&[data-variant=outlined]": {
                 "borderWidth": "3px",
                 "borderStyle": "solid",
                 "padding": "3px",
                 "borderColor": "inherit",
-                "&[color=success]": {
-                    "borderColor": "success.outlinedBorder",
-                    "color": "success.outlinedColor",
-                    "&[disabled]": {
-                        "borderColor": "success.outlinedDisabledBorder",
-                        "color": "success.outlinedDisabledColor",
-                    }
-                }
             }
         }
     })
+    .merge(sx! {
+        (theme.system_class()): variant_matrix(theme),
+    })
+}
+
+/// Creates the variant styling of every palette usable as a component color. These are the
+/// palettes of the built-in [`Color`]s, and any other palette that defines variant selectors.
+fn variant_matrix(theme: &Theme) -> Sx {
+    let built_in = Color::iter().map(|color| color.to_string()).collect::<Vec<_>>();
+    for palette_name in &built_in {
+        if theme.get_palette(palette_name).is_none() {
+            warn!("no palette for color {palette_name:?}, its variants are unstyled");
+        }
+    }
+    let mut custom = theme
+        .palettes()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| !built_in.contains(name))
+        .collect::<Vec<_>>();
+    custom.sort();
+
+    let required = variants::selector_names().count();
+    let mut emit = sx!();
+    for palette_name in built_in.iter().chain(&custom) {
+        let Some(palette) = theme.get_palette(palette_name) else {
+            continue;
+        };
+        let missing = variants::missing_selectors(palette);
+        if missing.is_empty() {
+            emit = emit.merge(variants::variant_sx(palette_name));
+        } else if built_in.contains(palette_name) || missing.len() < required {
+            error!("palette {palette_name:?} is missing the variant selectors {missing:?}");
+        }
+    }
+    emit
+}
+
+/// Creates the palette variables of every palette for a mode
+fn palette_vars(theme: &Theme, mode: &ThemeMode) -> Sx {
+    let mut vars = sx!();
+    for (palette_name, palette) in theme.palettes() {
+        for selector_name in palette.selectors() {
+            let mut selector = palette.select(selector_name, mode).unwrap().clone();
+            if let Ok(adjusted) = selector.to_rgba_color() {
+                selector = adjusted;
+            }
+            vars.insert(
+                theme.palette_var(palette_name, selector_name),
+                SxValue::Color(selector),
+            )
+        }
+    }
+    let color_scheme = match mode {
+        ThemeMode::Dark => "dark",
+        _ => "light",
+    };
+    vars.insert("colorScheme", SxValue::CssLiteral(color_scheme.to_string()));
+    vars
 }
 
 #[cfg(test)]
@@ -78,7 +153,84 @@ mod tests {
     #[test]
     fn create_light_baseline() {
         let theme = Theme::default();
-        let baseline = baseline(&theme, &ThemeMode::Light);
+        let baseline = baseline(&theme);
         println!("baseline: {baseline:#?}");
     }
+
+    #[test]
+    fn baseline_emits_scale_vars() {
+        let theme = Theme::default();
+        let css = baseline(&theme)
+            .to_css(&ThemeMode::Light, &theme)
+            .expect("could not create css");
+        assert!(css.contains("--textiler-spacing: 8px;"), "{css}");
+        assert!(css.contains("--textiler-radius-md: 8px;"), "{css}");
+        assert!(css.contains("--textiler-shadow-lg:"), "{css}");
+        assert!(css.contains("--textiler-z-index-modal: 1300;"), "{css}");
+    }
+
+    #[test]
+    fn baseline_emits_animations() {
+        let theme = Theme::default();
+        let css = baseline(&theme)
+            .to_css(&ThemeMode::Light, &theme)
+            .expect("could not create css");
+        assert_eq!(css.matches("@keyframes textiler-spin {").count(), 1, "{css}");
+    }
+
+    #[test]
+    fn baseline_emits_both_modes() {
+        let theme = Theme::default();
+        let css = baseline(&theme)
+            .to_css(&ThemeMode::Light, &theme)
+            .expect("could not create css");
+        let (light, dark) = css
+            .split_once("html[data-mode=dark] {")
+            .expect("should have a dark mode block");
+        let (dark, system) = dark
+            .split_once("@media (prefers-color-scheme: dark) {html:not([data-mode=light]) {")
+            .expect("should have a system dark mode block");
+        let var = theme.palette_var("text", "primary");
+        assert!(light.contains(&format!("{var}: var(--textiler-palette-neutral-080);")), "{css}");
+        assert!(light.contains("color-scheme: light;"), "{css}");
+        for dark in [dark, system] {
+            assert!(dark.contains(&format!("{var}: var(--textiler-palette-neutral-020);")), "{css}");
+            assert!(dark.contains("color-scheme: dark;"), "{css}");
+        }
+    }
+
+    #[test]
+    fn baseline_styles_every_variant_and_color() {
+        let theme = Theme::default();
+        let css = baseline(&theme)
+            .to_css(&ThemeMode::Light, &theme)
+            .expect("could not create css");
+        for variant in ["plain", "outlined", "soft", "solid"] {
+            for color in ["neutral", "primary", "success", "fatal", "warn"] {
+                let selector = format!(".textiler-system[data-variant={variant}][data-color={color}]");
+                assert!(css.contains(&format!("{selector} {{")), "missing {selector}: {css}");
+                assert!(css.contains(&format!("{selector}:hover {{")), "missing {selector}:hover");
+                assert!(
+                    css.contains(&format!("{selector}[aria-disabled=true]")),
+                    "missing disabled {selector}"
+                );
+            }
+        }
+        assert!(css.contains("var(--textiler-palette-warn-solid-bg)"), "{css}");
+    }
+
+    #[test]
+    fn baseline_styles_custom_palettes() {
+        let mut theme = Theme::default();
+        let primary = theme.get_palette("primary").cloned().unwrap();
+        theme.insert_palette("brand", primary);
+        let css = baseline(&theme)
+            .to_css(&ThemeMode::Light, &theme)
+            .expect("could not create css");
+        assert!(
+            css.contains(".textiler-system[data-variant=soft][data-color=brand] {"),
+            "{css}"
+        );
+        assert!(!css.contains("[data-color=common]"), "{css}");
+    }
 }
@@ -124,6 +124,95 @@ impl Breakpoints {
     pub fn points(&self) -> impl IntoIterator<Item = &Breakpoint> {
         self.points.iter()
     }
+
+    /// Gets the breakpoint following the given breakpoint, if any
+    pub fn next(&self, breakpoint: &str) -> Option<&Breakpoint> {
+        let current = self.get(breakpoint)?;
+        self.points.range(current..).nth(1)
+    }
+
+    /// A media query matching widths at and above the given breakpoint
+    pub fn up(&self, breakpoint: &str) -> Option<String> {
+        self.up_condition(breakpoint).map(media)
+    }
+
+    /// A media query matching widths up to the end of the given breakpoint, which is just before
+    /// the next breakpoint begins. Matches every width for the largest breakpoint.
+    pub fn down(&self, breakpoint: &str) -> Option<String> {
+        self.down_condition(breakpoint).map(media)
+    }
+
+    /// A media query matching widths from the start of the `start` breakpoint through the end of
+    /// the `end` breakpoint
+    pub fn between(&self, start: &str, end: &str) -> Option<String> {
+        self.between_condition(start, end).map(media)
+    }
+
+    /// A media query matching only the widths of the given breakpoint
+    pub fn only(&self, breakpoint: &str) -> Option<String> {
+        self.between(breakpoint, breakpoint)
+    }
+
+    /// Creates the media query for a breakpoint key, such as `md`, `md-up`, `md-down`,
+    /// `lg-only` or the range `sm-md`
+    pub fn query(&self, key: &str) -> Option<String> {
+        self.condition(key).map(media)
+    }
+
+    /// Creates a container query for a breakpoint key, using the same keys as
+    /// [`query`](Self::query). Queries the nearest container, or the named container if given.
+    pub fn container_query(&self, key: &str, container: Option<&str>) -> Option<String> {
+        let condition = self.condition(key)?;
+        Some(match container {
+            None => format!("@container {condition}"),
+            Some(container) => format!("@container {container} {condition}"),
+        })
+    }
+
+    fn condition(&self, key: &str) -> Option<String> {
+        match key.split_once('-') {
+            None => self.up_condition(key),
+            Some((bp, "up")) => self.up_condition(bp),
+            Some((bp, "down")) => self.down_condition(bp),
+            Some((bp, "only")) => self.between_condition(bp, bp),
+            Some((start, end)) => self.between_condition(start, end),
+        }
+    }
+
+    fn up_condition(&self, breakpoint: &str) -> Option<String> {
+        let bp = self.get(breakpoint)?;
+        Some(format!("(min-width: {}px)", bp.width))
+    }
+
+    fn down_condition(&self, breakpoint: &str) -> Option<String> {
+        let _ = self.get(breakpoint)?;
+        Some(match self.next(breakpoint) {
+            Some(next) => format!("(max-width: {})", upper_bound(next)),
+            None => "(min-width: 0px)".to_string(),
+        })
+    }
+
+    fn between_condition(&self, start: &str, end: &str) -> Option<String> {
+        let start = self.get(start)?;
+        let _ = self.get(end)?;
+        Some(match self.next(end) {
+            Some(next) => format!(
+                "(min-width: {}px) and (max-width: {})",
+                start.width,
+                upper_bound(next)
+            ),
+            None => format!("(min-width: {}px)", start.width),
+        })
+    }
+}
+
+fn media(condition: String) -> String {
+    format!("@media {condition}")
+}
+
+/// The upper bound of the range ending before `next`, a fraction of a pixel below its width
+fn upper_bound(next: &Breakpoint) -> String {
+    format!("{:.2}px", next.width as f32 - 0.02)
 }
 
 #[derive(Debug)]
@@ -166,4 +255,40 @@ mod tests {
         assert_eq!(bps.get("lg").unwrap().width(), 992);
         assert_eq!(bps.get("xl").unwrap().width(), 1200);
     }
+
+    #[test]
+    fn media_queries() {
+        let bps = Breakpoints::default();
+        assert_eq!(bps.up("md").unwrap(), "@media (min-width: 768px)");
+        assert_eq!(bps.down("md").unwrap(), "@media (max-width: 991.98px)");
+        assert_eq!(bps.down("xl").unwrap(), "@media (min-width: 0px)");
+        assert_eq!(
+            bps.between("sm", "md").unwrap(),
+            "@media (min-width: 600px) and (max-width: 991.98px)"
+        );
+        assert_eq!(
+            bps.only("sm").unwrap(),
+            "@media (min-width: 600px) and (max-width: 767.98px)"
+        );
+        assert_eq!(bps.only("xl").unwrap(), "@media (min-width: 1200px)");
+        assert_eq!(bps.query("lg-only"), bps.only("lg"));
+        assert_eq!(bps.query("md-down"), bps.down("md"));
+        assert_eq!(bps.query("sm-md"), bps.between("sm", "md"));
+        assert_eq!(bps.query("md"), bps.up("md"));
+        assert_eq!(bps.query("margin-top"), None);
+        assert_eq!(bps.up("xxl"), None);
+    }
+
+    #[test]
+    fn container_queries() {
+        let bps = Breakpoints::default();
+        assert_eq!(
+            bps.container_query("md", None).unwrap(),
+            "@container (min-width: 768px)"
+        );
+        assert_eq!(
+            bps.container_query("sm-only", Some("card")).unwrap(),
+            "@container card (min-width: 600px) and (max-width: 767.98px)"
+        );
+    }
 }
@@ -1,8 +1,10 @@
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_while_m_n};
-use nom::combinator::{map_res, opt};
-use nom::error::Error;
-use nom::sequence::tuple;
+use nom::bytes::complete::{tag, tag_no_case, take_while_m_n};
+use nom::character::complete::{alpha1, multispace0, multispace1};
+use nom::combinator::{all_consuming, map, map_res, opt, verify};
+use nom::error::{Error, ErrorKind};
+use nom::number::complete::recognize_float;
+use nom::sequence::{delimited, pair, preceded, tuple};
 use nom::{ErrorConvert, Finish, IResult};
 use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -11,6 +13,8 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use thiserror::Error;
 
+use crate::utils::bounded_float::BoundedFloat;
+
 /// Html compatible
 #[derive(Debug, Clone, PartialEq, Deserialize, Hash, Eq, Serialize)]
 #[serde(untagged)]
@@ -19,20 +23,41 @@ pub enum Color {
     CSSLiteral(String),
     /// A hex color
     Hex(u32),
-    /// Rgb color
-    Rgb { r: u8, g: u8, b: u8 },
+    // variants with alpha come first, otherwise untagged deserialization drops the alpha
     /// Rgba color
     Rgba { r: u8, g: u8, b: u8, a: u8 },
-    /// hsl color
-    Hsl { h: u16, s: u8, l: u8 },
+    /// Rgb color
+    Rgb { r: u8, g: u8, b: u8 },
     /// hsla color
     Hsla { h: u16, s: u8, l: u8, a: u8 },
+    /// hsl color
+    Hsl { h: u16, s: u8, l: u8 },
+    /// OKLab color, with a perceptual lightness in `[0, 1]`
+    Oklab {
+        l: BoundedFloat<0, 1>,
+        a: BoundedFloat<-1, 1>,
+        b: BoundedFloat<-1, 1>,
+        #[serde(default = "opaque")]
+        alpha: BoundedFloat<0, 1>,
+    },
+    /// OKLCH color, the polar form of OKLab with a hue in degrees
+    Oklch {
+        l: BoundedFloat<0, 1>,
+        c: BoundedFloat<0, 1>,
+        h: BoundedFloat<0, 360>,
+        #[serde(default = "opaque")]
+        alpha: BoundedFloat<0, 1>,
+    },
     Var {
         var: String,
         fallback: Option<Box<Color>>,
     },
 }
 
+fn opaque() -> BoundedFloat<0, 1> {
+    BoundedFloat::MAX
+}
+
 /// HSL are constrained to `[0, 1]`
 pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> [u8; 3] {
     let mut r: f32 = 0.;
@@ -108,6 +133,68 @@ pub fn rgb_to_hsl(r: u8, g: u8, b: u8) -> [f32; 3] {
     [h as f32, s as f32, l as f32]
 }
 
+fn srgb_to_linear(channel: u8) -> f32 {
+    let c = channel as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Converts an sRGB color into OKLab, returning `[l, a, b]`
+#[allow(clippy::excessive_precision)]
+pub fn rgb_to_oklab(r: u8, g: u8, b: u8) -> [f32; 3] {
+    let [r, g, b] = [r, g, b].map(srgb_to_linear);
+
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// Converts an OKLab color into sRGB, clamping colors outside of the sRGB gamut
+#[allow(clippy::excessive_precision)]
+pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> [u8; 3] {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+    .map(linear_to_srgb)
+}
+
+/// Converts OKLab into OKLCH, where the hue is in degrees
+pub fn oklab_to_oklch(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let c = (a * a + b * b).sqrt();
+    let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+    [l, c, h]
+}
+
+/// Converts OKLCH, where the hue is in degrees, into OKLab
+pub fn oklch_to_oklab(l: f32, c: f32, h: f32) -> [f32; 3] {
+    let h = h.to_radians();
+    [l, c * h.cos(), c * h.sin()]
+}
+
 #[derive(Debug)]
 pub(super) enum SimpleColor {
     Rgba(u8, u8, u8, u8),
@@ -129,10 +216,85 @@ impl Color {
         Self::Rgb { r, g, b }
     }
 
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::Rgba { r, g, b, a }
+    }
+
     pub fn hsl(h: u16, s: u8, l: u8) -> Self {
         Self::Hsl { h, s, l }
     }
 
+    /// Creates a new OKLab color, clamping each value into its range
+    pub fn oklab(l: f32, a: f32, b: f32) -> Self {
+        Self::Oklab {
+            l: BoundedFloat::clamped(l),
+            a: BoundedFloat::clamped(a),
+            b: BoundedFloat::clamped(b),
+            alpha: opaque(),
+        }
+    }
+
+    /// Creates a new OKLCH color, clamping each value into its range and wrapping the hue
+    pub fn oklch(l: f32, c: f32, h: f32) -> Self {
+        Self::Oklch {
+            l: BoundedFloat::clamped(l),
+            c: BoundedFloat::clamped(c),
+            h: BoundedFloat::clamped(h.rem_euclid(360.0)),
+            alpha: opaque(),
+        }
+    }
+
+    /// Gets the color as an OKLab value, as `[l, a, b, alpha]`, if it can be converted
+    pub fn to_oklab(&self) -> Result<[f32; 4], TransformColorError> {
+        match self {
+            &Color::Oklab { l, a, b, alpha } => Ok([*l, *a, *b, *alpha]),
+            &Color::Oklch { l, c, h, alpha } => {
+                let [l, a, b] = oklch_to_oklab(*l, *c, *h);
+                Ok([l, a, b, *alpha])
+            }
+            color => {
+                let [r, g, b, alpha] = color.to_rgba()?;
+                let [l, a, b] = rgb_to_oklab(r, g, b);
+                Ok([l, a, b, alpha as f32 / 255.0])
+            }
+        }
+    }
+
+    /// Gets the color as an OKLab color, if it can be converted
+    pub fn to_oklab_color(&self) -> Result<Color, TransformColorError> {
+        let [l, a, b, alpha] = self.to_oklab()?;
+        Ok(Self::Oklab {
+            l: BoundedFloat::clamped(l),
+            a: BoundedFloat::clamped(a),
+            b: BoundedFloat::clamped(b),
+            alpha: BoundedFloat::clamped(alpha),
+        })
+    }
+
+    /// Gets the color as an OKLCH value, as `[l, c, h, alpha]` with the hue in degrees, if it can
+    /// be converted
+    pub fn to_oklch(&self) -> Result<[f32; 4], TransformColorError> {
+        match self {
+            &Color::Oklch { l, c, h, alpha } => Ok([*l, *c, *h, *alpha]),
+            color => {
+                let [l, a, b, alpha] = color.to_oklab()?;
+                let [l, c, h] = oklab_to_oklch(l, a, b);
+                Ok([l, c, h, alpha])
+            }
+        }
+    }
+
+    /// Gets the color as an OKLCH color, if it can be converted
+    pub fn to_oklch_color(&self) -> Result<Color, TransformColorError> {
+        let [l, c, h, alpha] = self.to_oklch()?;
+        Ok(Self::Oklch {
+            l: BoundedFloat::clamped(l),
+            c: BoundedFloat::clamped(c),
+            h: BoundedFloat::clamped(h.rem_euclid(360.0)),
+            alpha: BoundedFloat::clamped(alpha),
+        })
+    }
+
     /// Gets the color as an HSLA value, where each is a value \[0,1], if it
     /// can be converted
     pub fn to_hsla(&self) -> Result<[f32; 4], TransformColorError> {
@@ -180,6 +342,57 @@ impl Color {
         Ok(Self::Rgba { r, g, b, a })
     }
 
+    /// Gets the WCAG relative luminance of this color in `[0, 1]`, ignoring alpha
+    pub fn relative_luminance(&self) -> Result<f32, TransformColorError> {
+        let [r, g, b, _] = self.to_rgba()?;
+        let [r, g, b] = [r, g, b].map(srgb_to_linear);
+        Ok(0.2126 * r + 0.7152 * g + 0.0722 * b)
+    }
+
+    /// Composites this color onto a backdrop, which is how a translucent color is seen on top of
+    /// another. Opaque colors are unchanged.
+    pub fn over(&self, backdrop: &Color) -> Result<Color, TransformColorError> {
+        let [r, g, b, a] = self.to_rgba()?;
+        let [br, bg, bb, ba] = backdrop.to_rgba()?;
+        let alpha = a as f32 / 255.0;
+        let backdrop_alpha = ba as f32 / 255.0 * (1.0 - alpha);
+        let out_alpha = alpha + backdrop_alpha;
+        if out_alpha == 0.0 {
+            return Ok(Color::rgba(0, 0, 0, 0));
+        }
+        let blend = |front: u8, back: u8| {
+            ((front as f32 * alpha + back as f32 * backdrop_alpha) / out_alpha).round() as u8
+        };
+        Ok(Color::rgba(
+            blend(r, br),
+            blend(g, bg),
+            blend(b, bb),
+            (out_alpha * 255.0).round() as u8,
+        ))
+    }
+
+    /// Gets the WCAG contrast ratio between this color and another, in `[1, 21]`
+    pub fn contrast_ratio(&self, other: &Color) -> Result<f32, TransformColorError> {
+        let this = self.relative_luminance()?;
+        let other = other.relative_luminance()?;
+        let (lighter, darker) = if this > other {
+            (this, other)
+        } else {
+            (other, this)
+        };
+        Ok((lighter + 0.05) / (darker + 0.05))
+    }
+
+    /// Picks the candidate with the highest contrast against this color, such as the text
+    /// color to use on top of a background. Candidates that can't be converted are skipped.
+    pub fn best_contrast<'a>(&self, candidates: &'a [Color]) -> Option<&'a Color> {
+        candidates
+            .iter()
+            .filter_map(|candidate| Some((candidate, self.contrast_ratio(candidate).ok()?)))
+            .max_by(|(_, left), (_, right)| left.total_cmp(right))
+            .map(|(candidate, _)| candidate)
+    }
+
     pub(super) fn to_simple(&self) -> Result<SimpleColor, TransformColorError> {
         let mut color: Cow<Color> = Cow::Borrowed(self);
         if let Color::CSSLiteral(literal) = self {
@@ -189,7 +402,7 @@ impl Color {
         let simple = match &*color {
             &Color::Hex(hex) => {
                 let [r, g, b] = u32_to_rgb(hex);
-                SimpleColor::Rgba(r, g, b, 0)
+                SimpleColor::Rgba(r, g, b, 255)
             }
             &Color::Rgb { r, g, b } => SimpleColor::Rgba(r, g, b, 255),
             &Color::Rgba { r, g, b, a } => SimpleColor::Rgba(r, g, b, a),
@@ -202,6 +415,15 @@ impl Color {
                 l as f32 / 100.0,
                 a as f32 / 100.0,
             ),
+            &Color::Oklab { l, a, b, alpha } => {
+                let [r, g, b] = oklab_to_rgb(*l, *a, *b);
+                SimpleColor::Rgba(r, g, b, (*alpha * 255.0).round() as u8)
+            }
+            &Color::Oklch { l, c, h, alpha } => {
+                let [l, a, b] = oklch_to_oklab(*l, *c, *h);
+                let [r, g, b] = oklab_to_rgb(l, a, b);
+                SimpleColor::Rgba(r, g, b, (*alpha * 255.0).round() as u8)
+            }
             color => return Err(TransformColorError::NonEligibleColor(color.clone())),
         };
         Ok(simple)
@@ -221,7 +443,8 @@ impl FromStr for Color {
     type Err = ParseColorError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_color(s).finish().map(|ok| ok.1).map_err(|e| {
+        let trimmed = s.trim();
+        all_consuming(parse_color)(trimmed).finish().map(|ok| ok.1).map_err(|e| {
             let Error { input, code } = e;
 
             Error {
@@ -254,6 +477,18 @@ impl Display for Color {
             Color::Hsla { h, s, l, a } => {
                 write!(f, "hsla({h}, {s}%, {l}%, {:1.2})", *a as f32 / 100.0)
             }
+            Color::Oklab { l, a, b, alpha } if **alpha == 1.0 => {
+                write!(f, "oklab({l} {a} {b})")
+            }
+            Color::Oklab { l, a, b, alpha } => {
+                write!(f, "oklab({l} {a} {b} / {alpha})")
+            }
+            Color::Oklch { l, c, h, alpha } if **alpha == 1.0 => {
+                write!(f, "oklch({l} {c} {h})")
+            }
+            Color::Oklch { l, c, h, alpha } => {
+                write!(f, "oklch({l} {c} {h} / {alpha})")
+            }
             Color::Var {
                 var: name,
                 fallback,
@@ -269,36 +504,303 @@ impl Display for Color {
     }
 }
 
+/// Parses a css color, supporting hex codes, `rgb[a]()`, `hsl[a]()`, `hwb()`, named colors and
+/// `transparent` in both their comma and space separated forms.
 pub fn parse_color(color: &str) -> IResult<&str, Color> {
-    alt((parse_hex_color,))(color)
+    alt((
+        parse_hex_color,
+        parse_rgb_function,
+        parse_hsl_function,
+        parse_hwb_function,
+        parse_oklab_function,
+        parse_oklch_function,
+        parse_named_color,
+    ))(color)
 }
 
 fn is_hex_digit(c: char) -> bool {
-    c.is_digit(16)
+    c.is_ascii_hexdigit()
 }
 
-fn from_hex(input: &str) -> Result<u8, std::num::ParseIntError> {
-    u8::from_str_radix(input, 16)
+fn parse_hex_color(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = tag("#")(color)?;
+    let (rest, digits) = verify(take_while_m_n(3, 8, is_hex_digit), |digits: &str| {
+        matches!(digits.len(), 3 | 4 | 6 | 8)
+    })(rest)?;
+
+    let channels = match digits.len() {
+        3 | 4 => digits
+            .chars()
+            .map(|c| {
+                let digit = c.to_digit(16).expect("verified hex digit") as u8;
+                digit * 16 + digit
+            })
+            .collect::<Vec<_>>(),
+        _ => digits
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                u8::from_str_radix(std::str::from_utf8(pair).expect("ascii"), 16)
+                    .expect("verified hex digits")
+            })
+            .collect::<Vec<_>>(),
+    };
+
+    Ok((
+        rest,
+        match channels[..] {
+            [r, g, b] => Color::Rgb { r, g, b },
+            [r, g, b, a] => Color::Rgba { r, g, b, a },
+            _ => unreachable!("hex colors have 3 or 4 channels"),
+        },
+    ))
 }
 
-fn hex_primary(input: &str) -> IResult<&str, u8> {
-    map_res(take_while_m_n(2, 2, is_hex_digit), from_hex)(input)
+fn parse_named_color(color: &str) -> IResult<&str, Color> {
+    let (rest, name) = alpha1(color)?;
+    let name = name.to_ascii_lowercase();
+    if name == "transparent" {
+        return Ok((
+            rest,
+            Color::Rgba {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+        ));
+    }
+    match cssparser::color::parse_named_color(&name) {
+        Ok((r, g, b)) => Ok((rest, Color::Rgb { r, g, b })),
+        Err(()) => Err(nom::Err::Error(Error::new(color, ErrorKind::Tag))),
+    }
 }
 
-fn parse_hex_color(color: &str) -> IResult<&str, Color> {
-    let (rest, _) = tag("#")(color)?;
-    let (rest, (r, g, b, a)) =
-        tuple((hex_primary, hex_primary, hex_primary, opt(hex_primary)))(rest)?;
+/// A single argument of a css color function
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorComponent {
+    Number(f32),
+    Percent(f32),
+    /// An angle, in degrees
+    Angle(f32),
+}
+
+impl ColorComponent {
+    /// Gets this component as an rgb channel in `[0, 255]`
+    fn to_channel(self) -> u8 {
+        match self {
+            ColorComponent::Number(n) | ColorComponent::Angle(n) => n,
+            ColorComponent::Percent(p) => p / 100.0 * 255.0,
+        }
+        .clamp(0.0, 255.0)
+        .round() as u8
+    }
+
+    /// Gets this component as a fraction in `[0, 1]`, where bare numbers are fractions.
+    fn to_alpha(self) -> f32 {
+        match self {
+            ColorComponent::Number(n) | ColorComponent::Angle(n) => n,
+            ColorComponent::Percent(p) => p / 100.0,
+        }
+        .clamp(0.0, 1.0)
+    }
+
+    /// Gets this component as a fraction in `[0, 1]`, where bare numbers are percentages.
+    fn to_fraction(self) -> f32 {
+        match self {
+            ColorComponent::Number(n) | ColorComponent::Angle(n) | ColorComponent::Percent(n) => {
+                n / 100.0
+            }
+        }
+        .clamp(0.0, 1.0)
+    }
+
+    /// Gets this component as a perceptual lightness in `[0, 1]`, where bare numbers are
+    /// fractions.
+    fn to_lightness(self) -> f32 {
+        self.to_alpha()
+    }
+
+    /// Gets this component as an OKLab axis or chroma, where `100%` is `0.4`
+    fn to_chroma(self) -> f32 {
+        match self {
+            ColorComponent::Number(n) | ColorComponent::Angle(n) => n,
+            ColorComponent::Percent(p) => p / 100.0 * 0.4,
+        }
+    }
+
+    /// Gets this component as a hue in degrees, within `[0, 360)`
+    fn to_hue(self) -> f32 {
+        match self {
+            ColorComponent::Number(n) | ColorComponent::Angle(n) => n,
+            ColorComponent::Percent(p) => p / 100.0 * 360.0,
+        }
+        .rem_euclid(360.0)
+    }
+}
+
+fn parse_number(input: &str) -> IResult<&str, f32> {
+    map_res(recognize_float, f32::from_str)(input)
+}
+
+fn parse_color_component(input: &str) -> IResult<&str, ColorComponent> {
+    alt((
+        map(tag_no_case("none"), |_| ColorComponent::Number(0.0)),
+        map(
+            pair(
+                parse_number,
+                opt(alt((
+                    tag("%"),
+                    tag_no_case("deg"),
+                    tag_no_case("grad"),
+                    tag_no_case("rad"),
+                    tag_no_case("turn"),
+                ))),
+            ),
+            |(value, unit)| match unit.map(|unit| unit.to_ascii_lowercase()).as_deref() {
+                None => ColorComponent::Number(value),
+                Some("%") => ColorComponent::Percent(value),
+                Some("deg") => ColorComponent::Angle(value),
+                Some("grad") => ColorComponent::Angle(value * 0.9),
+                Some("rad") => ColorComponent::Angle(value.to_degrees()),
+                Some("turn") => ColorComponent::Angle(value * 360.0),
+                Some(_) => unreachable!("only known units are parsed"),
+            },
+        ),
+    ))(input)
+}
+
+/// Parses the arguments of a color function, either comma separated as `(a, b, c[, alpha])` or
+/// space separated as `(a b c[ / alpha])`.
+fn parse_color_arguments(
+    input: &str,
+) -> IResult<&str, ([ColorComponent; 3], Option<ColorComponent>)> {
+    let comma = || tuple((multispace0, tag(","), multispace0));
+    let legacy = tuple((
+        parse_color_component,
+        preceded(comma(), parse_color_component),
+        preceded(comma(), parse_color_component),
+        opt(preceded(comma(), parse_color_component)),
+    ));
+    let modern = tuple((
+        parse_color_component,
+        preceded(multispace1, parse_color_component),
+        preceded(multispace1, parse_color_component),
+        opt(preceded(
+            tuple((multispace0, tag("/"), multispace0)),
+            parse_color_component,
+        )),
+    ));
+
+    map(
+        delimited(
+            pair(tag("("), multispace0),
+            alt((legacy, modern)),
+            pair(multispace0, tag(")")),
+        ),
+        |(first, second, third, alpha)| ([first, second, third], alpha),
+    )(input)
+}
+
+fn parse_rgb_function(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = pair(tag_no_case("rgb"), opt(tag_no_case("a")))(color)?;
+    let (rest, ([r, g, b], alpha)) = parse_color_arguments(rest)?;
+    let [r, g, b] = [r, g, b].map(ColorComponent::to_channel);
 
     Ok((
         rest,
-        match a {
+        match alpha {
             None => Color::Rgb { r, g, b },
-            Some(a) => Color::Rgba { r, g, b, a },
+            Some(alpha) => Color::Rgba {
+                r,
+                g,
+                b,
+                a: (alpha.to_alpha() * 255.0).round() as u8,
+            },
         },
     ))
 }
 
+fn parse_hsl_function(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = pair(tag_no_case("hsl"), opt(tag_no_case("a")))(color)?;
+    let (rest, ([h, s, l], alpha)) = parse_color_arguments(rest)?;
+    let h = (h.to_hue().round() as u16) % 360;
+    let s = (s.to_fraction() * 100.0).round() as u8;
+    let l = (l.to_fraction() * 100.0).round() as u8;
+
+    Ok((
+        rest,
+        match alpha {
+            None => Color::Hsl { h, s, l },
+            Some(alpha) => Color::Hsla {
+                h,
+                s,
+                l,
+                a: (alpha.to_alpha() * 100.0).round() as u8,
+            },
+        },
+    ))
+}
+
+fn parse_hwb_function(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = tag_no_case("hwb")(color)?;
+    let (rest, ([h, w, b], alpha)) = parse_color_arguments(rest)?;
+    let [r, g, b] = hwb_to_rgb(h.to_hue() / 360.0, w.to_fraction(), b.to_fraction());
+
+    Ok((
+        rest,
+        match alpha {
+            None => Color::Rgb { r, g, b },
+            Some(alpha) => Color::Rgba {
+                r,
+                g,
+                b,
+                a: (alpha.to_alpha() * 255.0).round() as u8,
+            },
+        },
+    ))
+}
+
+fn parse_oklab_function(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = tag_no_case("oklab")(color)?;
+    let (rest, ([l, a, b], alpha)) = parse_color_arguments(rest)?;
+    let color = Color::oklab(l.to_lightness(), a.to_chroma(), b.to_chroma());
+
+    Ok((rest, with_alpha(color, alpha)))
+}
+
+fn parse_oklch_function(color: &str) -> IResult<&str, Color> {
+    let (rest, _) = tag_no_case("oklch")(color)?;
+    let (rest, ([l, c, h], alpha)) = parse_color_arguments(rest)?;
+    let color = Color::oklch(l.to_lightness(), c.to_chroma(), h.to_hue());
+
+    Ok((rest, with_alpha(color, alpha)))
+}
+
+fn with_alpha(color: Color, alpha: Option<ColorComponent>) -> Color {
+    let Some(alpha) = alpha else {
+        return color;
+    };
+    let alpha = BoundedFloat::clamped(alpha.to_alpha());
+    match color {
+        Color::Oklab { l, a, b, .. } => Color::Oklab { l, a, b, alpha },
+        Color::Oklch { l, c, h, .. } => Color::Oklch { l, c, h, alpha },
+        color => color,
+    }
+}
+
+/// HWB are constrained to `[0, 1]`
+pub fn hwb_to_rgb(h: f32, w: f32, b: f32) -> [u8; 3] {
+    if w + b >= 1.0 {
+        let gray = (w / (w + b) * 255.0).round() as u8;
+        return [gray, gray, gray];
+    }
+    hsl_to_rgb(h, 1.0, 0.5).map(|channel| {
+        let channel = channel as f32 / 255.0;
+        ((channel * (1.0 - w - b) + w) * 255.0).round() as u8
+    })
+}
+
 fn u32_to_rgb(value: u32) -> [u8; 3] {
     let r = (value >> 16) & 0xFF;
     let g = (value >> 8) & 0xFF;
@@ -336,6 +838,177 @@ mod tests {
         println!("{parsed}")
     }
 
+    #[test]
+    fn parse_hex_shorthand_colors() {
+        assert_eq!(
+            "#abc".parse::<Color>().unwrap(),
+            Color::Rgb {
+                r: 0xaa,
+                g: 0xbb,
+                b: 0xcc
+            }
+        );
+        assert_eq!(
+            "#abc8".parse::<Color>().unwrap(),
+            Color::Rgba {
+                r: 0xaa,
+                g: 0xbb,
+                b: 0xcc,
+                a: 0x88
+            }
+        );
+        assert!("#abcde".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn parse_rgb_functions() {
+        let expected = Color::Rgba {
+            r: 10,
+            g: 20,
+            b: 30,
+            a: 128,
+        };
+        assert_eq!("rgb(10 20 30 / 50%)".parse::<Color>().unwrap(), expected);
+        assert_eq!("rgba(10, 20, 30, 0.5)".parse::<Color>().unwrap(), expected);
+        assert_eq!("RGB(10,20,30,.5)".parse::<Color>().unwrap(), expected);
+        assert_eq!(
+            "rgb(100%, 0%, 50%)".parse::<Color>().unwrap(),
+            Color::Rgb { r: 255, g: 0, b: 128 }
+        );
+        assert!("rgb(10 20)".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn parse_hsl_functions() {
+        assert_eq!(
+            "hsl(120deg 50% 25%)".parse::<Color>().unwrap(),
+            Color::Hsl { h: 120, s: 50, l: 25 }
+        );
+        assert_eq!(
+            "hsla(0.5turn, 100%, 50%, 0.25)".parse::<Color>().unwrap(),
+            Color::Hsla {
+                h: 180,
+                s: 100,
+                l: 50,
+                a: 25
+            }
+        );
+        assert_eq!(
+            "hsl(-120 50% 25%)".parse::<Color>().unwrap(),
+            Color::Hsl { h: 240, s: 50, l: 25 }
+        );
+    }
+
+    #[test]
+    fn parse_hwb_functions() {
+        assert_eq!(
+            "hwb(0 0% 0%)".parse::<Color>().unwrap(),
+            Color::Rgb { r: 255, g: 0, b: 0 }
+        );
+        assert_eq!(
+            "hwb(90deg 60% 60% / 1)".parse::<Color>().unwrap(),
+            Color::Rgba {
+                r: 128,
+                g: 128,
+                b: 128,
+                a: 255
+            }
+        );
+    }
+
+    #[test]
+    fn parse_named_colors() {
+        assert_eq!(
+            "rebeccapurple".parse::<Color>().unwrap(),
+            Color::Rgb {
+                r: 102,
+                g: 51,
+                b: 153
+            }
+        );
+        assert_eq!(
+            "Transparent".parse::<Color>().unwrap(),
+            Color::Rgba {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0
+            }
+        );
+        assert!("notacolor".parse::<Color>().is_err());
+        assert_eq!(
+            Color::named("red").to_rgba().unwrap(),
+            [255, 0, 0, 255]
+        );
+    }
+
+    #[test]
+    fn oklab_round_trip() {
+        for color in [
+            Color::rgb(255, 0, 0),
+            Color::rgb(12, 200, 95),
+            Color::rgb(255, 255, 255),
+            Color::rgb(0, 0, 0),
+        ] {
+            let oklab = color.to_oklab_color().unwrap();
+            assert_eq!(oklab.to_rgba().unwrap(), color.to_rgba().unwrap());
+            let oklch = color.to_oklch_color().unwrap();
+            assert_eq!(oklch.to_rgba().unwrap(), color.to_rgba().unwrap());
+        }
+
+        let [l, a, b, _] = Color::rgb(255, 255, 255).to_oklab().unwrap();
+        assert!((l - 1.0).abs() < 0.001);
+        assert!(a.abs() < 0.001 && b.abs() < 0.001);
+    }
+
+    #[test]
+    fn parse_oklab_functions() {
+        let Color::Oklch { l, c, h, alpha } = "oklch(62.8% 0.2577 29.23 / 50%)".parse().unwrap()
+        else {
+            panic!("should parse oklch");
+        };
+        assert!((*l - 0.628).abs() < 0.0001);
+        assert!((*c - 0.2577).abs() < 0.0001);
+        assert!((*h - 29.23).abs() < 0.0001);
+        assert_eq!(*alpha, 0.5);
+
+        let red = "oklab(0.628 0.2249 0.1258)".parse::<Color>().unwrap();
+        assert_eq!(red.to_rgba().unwrap(), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn contrast_ratios() {
+        let white = Color::rgb(255, 255, 255);
+        let black = Color::rgb(0, 0, 0);
+        assert!((white.contrast_ratio(&black).unwrap() - 21.0).abs() < 0.001);
+        assert!((black.contrast_ratio(&white).unwrap() - 21.0).abs() < 0.001);
+        assert!((white.contrast_ratio(&white).unwrap() - 1.0).abs() < 0.001);
+
+        let gray = Color::hex_code(0x767676);
+        assert!((white.contrast_ratio(&gray).unwrap() - 4.54).abs() < 0.01);
+    }
+
+    #[test]
+    fn composites_over_backdrop() {
+        let white = Color::rgb(255, 255, 255);
+        let half_black = Color::rgba(0, 0, 0, 128);
+        let gray = half_black.over(&white).unwrap();
+        assert_eq!(gray.to_rgba().unwrap(), [127, 127, 127, 255]);
+        let opaque = white.over(&half_black).unwrap();
+        assert_eq!(opaque.to_rgba().unwrap(), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn best_contrast_picks_on_color() {
+        let white = Color::rgb(255, 255, 255);
+        let black = Color::rgb(0, 0, 0);
+        let candidates = [white.clone(), black.clone()];
+
+        assert_eq!(Color::hex_code(0x139513).best_contrast(&candidates), Some(&black));
+        assert_eq!(Color::hex_code(0x1a237e).best_contrast(&candidates), Some(&white));
+        assert_eq!(white.best_contrast(&[]), None);
+    }
+
     #[test]
     fn hsl_to_rgb_correctness() {
         let (h, s, l) = (126.0 / 360., 0.46, 0.63);
@@ -0,0 +1,337 @@
+//! WCAG contrast checking for the colors of a [`Theme`](Theme)
+
+use std::fmt::{Display, Formatter};
+
+use crate::theme::theme_mode::ThemeMode;
+use crate::theme::{Color, Theme};
+
+/// A WCAG conformance level
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ContrastLevel {
+    /// The minimum level, requiring `4.5:1` for text
+    #[default]
+    AA,
+    /// The enhanced level, requiring `7:1` for text
+    AAA,
+}
+
+/// What a foreground color is used for, which determines the contrast it needs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ContrastKind {
+    /// Normal sized text
+    #[default]
+    Text,
+    /// Large text, at least 18pt or 14pt bold
+    LargeText,
+    /// User interface components, such as borders
+    NonText,
+}
+
+impl ContrastKind {
+    /// Gets the minimum contrast ratio required at a given level
+    pub fn required_ratio(&self, level: ContrastLevel) -> f32 {
+        match (self, level) {
+            (ContrastKind::Text, ContrastLevel::AA) => 4.5,
+            (ContrastKind::Text, ContrastLevel::AAA) => 7.0,
+            (ContrastKind::LargeText, ContrastLevel::AA) => 3.0,
+            (ContrastKind::LargeText, ContrastLevel::AAA) => 4.5,
+            (ContrastKind::NonText, _) => 3.0,
+        }
+    }
+}
+
+/// A foreground and background selector that are expected to be legible together
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContrastPair {
+    /// The foreground, as a `palette.selector` token
+    pub foreground: String,
+    /// The background, as a `palette.selector` token
+    pub background: String,
+    /// What the foreground is used for
+    pub kind: ContrastKind,
+}
+
+impl ContrastPair {
+    /// Creates a new pair from two `palette.selector` tokens
+    pub fn new(
+        foreground: impl AsRef<str>,
+        background: impl AsRef<str>,
+        kind: ContrastKind,
+    ) -> Self {
+        Self {
+            foreground: foreground.as_ref().to_string(),
+            background: background.as_ref().to_string(),
+            kind,
+        }
+    }
+}
+
+/// A pair that failed an audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastFailure {
+    /// The pair that failed
+    pub pair: ContrastPair,
+    /// The mode the pair failed in
+    pub mode: ThemeMode,
+    /// Why the pair failed
+    pub problem: ContrastProblem,
+}
+
+/// Why a pair failed an audit
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContrastProblem {
+    /// The contrast ratio is lower than required
+    InsufficientContrast { ratio: f32, required: f32 },
+    /// The given token could not be resolved to a concrete color
+    Unresolved(String),
+}
+
+impl Display for ContrastFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ContrastPair {
+            foreground,
+            background,
+            ..
+        } = &self.pair;
+        write!(f, "{foreground} on {background} ({:?}): ", self.mode)?;
+        match &self.problem {
+            ContrastProblem::InsufficientContrast { ratio, required } => {
+                write!(f, "contrast is {ratio:.2}:1, but {required}:1 is required")
+            }
+            ContrastProblem::Unresolved(token) => {
+                write!(f, "{token} could not be resolved to a color")
+            }
+        }
+    }
+}
+
+/// Audits pairs of palette selectors for WCAG contrast in both dark and light modes. Translucent
+/// foregrounds are composited onto their background, and translucent backgrounds onto
+/// `background.body`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContrastAudit {
+    level: ContrastLevel,
+    pairs: Vec<ContrastPair>,
+}
+
+impl ContrastAudit {
+    /// Creates an audit with no pairs
+    pub fn new(level: ContrastLevel) -> Self {
+        Self {
+            level,
+            pairs: vec![],
+        }
+    }
+
+    /// Creates an audit pairing selectors by the theme's naming conventions:
+    /// * every `text` selector on every `background` selector
+    /// * `{variant}Color` on `{variant}Bg` in the same palette, or on `background.body` if the
+    ///   palette has no such background
+    /// * `{variant}Border` on `background.body`, as a non-text pair
+    ///
+    /// Disabled selectors are skipped, as WCAG does not require contrast for inactive components.
+    pub fn for_theme(theme: &Theme, level: ContrastLevel) -> Self {
+        let mut audit = Self::new(level);
+        let backgrounds = theme
+            .get_palette("background")
+            .map(|palette| palette.selectors().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        if let Some(text) = theme.get_palette("text") {
+            for selector in text.selectors() {
+                for background in &backgrounds {
+                    audit = audit.pair(
+                        format!("text.{selector}"),
+                        format!("background.{background}"),
+                        ContrastKind::Text,
+                    );
+                }
+            }
+        }
+
+        for (palette_name, palette) in theme.palettes() {
+            for selector in palette.selectors().filter(|s| !s.contains("Disabled")) {
+                if let Some(variant) = selector.strip_suffix("Color") {
+                    let background = format!("{variant}Bg");
+                    let background = if palette.selectors().any(|s| s == background) {
+                        format!("{palette_name}.{background}")
+                    } else {
+                        "background.body".to_string()
+                    };
+                    audit = audit.pair(
+                        format!("{palette_name}.{selector}"),
+                        background,
+                        ContrastKind::Text,
+                    );
+                } else if selector.ends_with("Border") {
+                    audit = audit.pair(
+                        format!("{palette_name}.{selector}"),
+                        "background.body",
+                        ContrastKind::NonText,
+                    );
+                }
+            }
+        }
+
+        audit
+    }
+
+    /// Adds a pair to this audit
+    pub fn pair(
+        mut self,
+        foreground: impl AsRef<str>,
+        background: impl AsRef<str>,
+        kind: ContrastKind,
+    ) -> Self {
+        self.pairs
+            .push(ContrastPair::new(foreground, background, kind));
+        self
+    }
+
+    /// Gets the pairs checked by this audit
+    pub fn pairs(&self) -> &[ContrastPair] {
+        &self.pairs
+    }
+
+    /// Runs the audit against a theme, returning every failing pair
+    pub fn run(&self, theme: &Theme) -> Vec<ContrastFailure> {
+        let mut failures = vec![];
+        for mode in [ThemeMode::Dark, ThemeMode::Light] {
+            for pair in &self.pairs {
+                if let Some(problem) = self.check(theme, pair, &mode) {
+                    failures.push(ContrastFailure {
+                        pair: pair.clone(),
+                        mode: mode.clone(),
+                        problem,
+                    });
+                }
+            }
+        }
+        failures
+    }
+
+    fn check(
+        &self,
+        theme: &Theme,
+        pair: &ContrastPair,
+        mode: &ThemeMode,
+    ) -> Option<ContrastProblem> {
+        let resolve = |token: &str| {
+            token
+                .split_once('.')
+                .and_then(|(palette, selector)| theme.resolve_color(palette, selector, mode))
+                .ok_or_else(|| ContrastProblem::Unresolved(token.to_string()))
+        };
+        let foreground = match resolve(&pair.foreground) {
+            Ok(color) => color,
+            Err(problem) => return Some(problem),
+        };
+        let background = match resolve(&pair.background) {
+            Ok(color) => color,
+            Err(problem) => return Some(problem),
+        };
+        // translucent colors are seen composited onto what's behind them, which is the body for
+        // backgrounds, or an opaque white page if the body is translucent or missing as well
+        let page = Color::rgb(255, 255, 255);
+        let body = theme
+            .resolve_color("background", "body", mode)
+            .and_then(|body| body.over(&page).ok())
+            .unwrap_or(page);
+        let Ok(background) = background.over(&body) else {
+            return Some(ContrastProblem::Unresolved(pair.background.clone()));
+        };
+        let Ok(ratio) = foreground
+            .over(&background)
+            .and_then(|foreground| foreground.contrast_ratio(&background))
+        else {
+            return Some(ContrastProblem::Unresolved(pair.foreground.clone()));
+        };
+        let required = pair.kind.required_ratio(self.level);
+        (ratio < required).then_some(ContrastProblem::InsufficientContrast { ratio, required })
+    }
+}
+
+impl Theme {
+    /// Audits this theme's palettes for WCAG contrast using its naming conventions. See
+    /// [`ContrastAudit::for_theme`](ContrastAudit::for_theme) for which selectors are paired.
+    pub fn audit_contrast(&self, level: ContrastLevel) -> Vec<ContrastFailure> {
+        ContrastAudit::for_theme(self, level).run(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theme::palette::Palette;
+
+    use super::*;
+
+    #[test]
+    fn default_theme_text_is_legible() {
+        let theme = Theme::default();
+        let failures = ContrastAudit::new(ContrastLevel::AA)
+            .pair("text.primary", "background.body", ContrastKind::Text)
+            .run(&theme);
+        assert!(failures.is_empty(), "{failures:#?}");
+
+        // every paired selector resolves, even if not all of them reach the required contrast yet
+        let unresolved = theme
+            .audit_contrast(ContrastLevel::AAA)
+            .into_iter()
+            .filter(|failure| matches!(failure.problem, ContrastProblem::Unresolved(_)))
+            .map(|failure| failure.to_string())
+            .collect::<Vec<_>>();
+        assert!(unresolved.is_empty(), "{}", unresolved.join("\n"));
+    }
+
+    #[test]
+    fn composites_translucent_colors() {
+        let mut theme = Theme::new();
+        theme
+            .palette("background")
+            .insert_constant("body", Color::rgb(255, 255, 255));
+        let mut text = Palette::new();
+        text.insert_constant("primary", Color::rgba(0, 0, 0, 51));
+        text.insert_constant("secondary", Color::rgba(0, 0, 0, 230));
+        theme.insert_palette("text", text);
+
+        let failures = theme.audit_contrast(ContrastLevel::AA);
+        assert_eq!(failures.len(), 2, "{failures:#?}");
+        assert!(failures
+            .iter()
+            .all(|failure| failure.pair.foreground == "text.primary"));
+    }
+
+    #[test]
+    fn reports_failures_by_mode() {
+        let mut theme = Theme::new();
+        theme.palette("background").insert_by_mode(
+            "body",
+            Color::rgb(0, 0, 0),
+            Color::rgb(255, 255, 255),
+        );
+        let mut text = Palette::new();
+        text.insert_constant("primary", Color::rgb(0x30, 0x30, 0x30));
+        theme.insert_palette("text", text);
+
+        let failures = theme.audit_contrast(ContrastLevel::AA);
+        assert_eq!(failures.len(), 1, "{failures:#?}");
+        assert_eq!(failures[0].mode, ThemeMode::Dark);
+        assert!(matches!(
+            failures[0].problem,
+            ContrastProblem::InsufficientContrast { .. }
+        ));
+    }
+
+    #[test]
+    fn reports_unresolved_tokens() {
+        let theme = Theme::new();
+        let failures = ContrastAudit::new(ContrastLevel::AA)
+            .pair("text.primary", "background.body", ContrastKind::Text)
+            .run(&theme);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].problem,
+            ContrastProblem::Unresolved("text.primary".to_string())
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::theme::color::SimpleColor;
+use crate::theme::color::{SimpleColor, TransformColorError};
 use crate::theme::Color;
 use crate::utils::bounded_float::BoundedFloat;
 use indexmap::IndexMap;
@@ -13,6 +13,75 @@ pub struct Gradient {
     #[serde(flatten)]
     #[serde(deserialize_with = "de_gradient")]
     points: BTreeMap<BoundedFloat<0, 1>, Color>,
+    #[serde(skip)]
+    space: Option<ColorSpace>,
+    #[serde(skip)]
+    hue: HueInterpolation,
+}
+
+/// The color space colors within a gradient are interpolated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorSpace {
+    /// Interpolates red, green and blue channels
+    Rgb,
+    /// Interpolates hue, saturation and lightness
+    Hsl,
+    /// Interpolates in the perceptual OKLab space
+    Oklab,
+    /// Interpolates in the polar form of OKLab, keeping chroma even across hues
+    Oklch,
+}
+
+/// How hues are interpolated within polar color spaces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HueInterpolation {
+    /// Takes the shortest path around the color wheel
+    #[default]
+    Shorter,
+    /// Takes the longest path around the color wheel
+    Longer,
+}
+
+impl ColorSpace {
+    /// Converts a color into this color space
+    pub fn convert(&self, color: &Color) -> Result<Color, TransformColorError> {
+        match self {
+            ColorSpace::Rgb => color.to_rgba_color(),
+            ColorSpace::Hsl => color.to_hsla_color(),
+            ColorSpace::Oklab => color.to_oklab_color(),
+            ColorSpace::Oklch => color.to_oklch_color(),
+        }
+    }
+}
+
+impl HueInterpolation {
+    /// Interpolates between two hues in degrees, returning a hue in `[0, 360)`
+    pub fn interpolate(&self, from: f32, to: f32, t: f32) -> f32 {
+        let mut delta = to - from;
+        match self {
+            HueInterpolation::Shorter => {
+                if delta > 180.0 {
+                    delta -= 360.0;
+                } else if delta < -180.0 {
+                    delta += 360.0;
+                }
+            }
+            HueInterpolation::Longer => {
+                if 0.0 < delta && delta < 180.0 {
+                    delta -= 360.0;
+                } else if -180.0 < delta && delta <= 0.0 {
+                    delta += 360.0;
+                }
+            }
+        }
+        (from + delta * t).rem_euclid(360.0)
+    }
+}
+
+fn lerp(low: f32, high: f32, t: f32) -> f32 {
+    (high - low) * t + low
 }
 
 fn de_gradient<'de, D: Deserializer<'de>>(
@@ -55,6 +124,30 @@ impl Gradient {
         ])
     }
 
+    /// Sets the color space used between points
+    pub fn with_space(self, space: ColorSpace) -> Self {
+        Self {
+            space: Some(space),
+            ..self
+        }
+    }
+
+    /// Sets how hues are interpolated between points
+    pub fn with_hue_interpolation(self, hue: HueInterpolation) -> Self {
+        Self { hue, ..self }
+    }
+
+    /// Gets the color space used between points. When unset, hsl colors are interpolated in
+    /// [`Hsl`](ColorSpace::Hsl) and all others in [`Rgb`](ColorSpace::Rgb).
+    pub fn space(&self) -> Option<ColorSpace> {
+        self.space
+    }
+
+    /// Gets how hues are interpolated
+    pub fn hue_interpolation(&self) -> HueInterpolation {
+        self.hue
+    }
+
     fn calc_color_at(&self, bounded_float: &BoundedFloat<0, 1>) -> Option<Color> {
         let (&high_pt, high) = self.points.range(*bounded_float..).next()?;
         let (&low_pt, low) = self.points.range(..=*bounded_float).rev().next()?;
@@ -63,34 +156,58 @@ impl Gradient {
         }
 
         let color_pt: f32 = (*bounded_float - low_pt) / (high_pt - low_pt);
-        match (low.to_simple().ok()?, high.to_simple().ok()?) {
-            (SimpleColor::Hsla(l_h, l_s, l_l, l_a), SimpleColor::Hsla(h_h, h_s, h_l, h_a)) => {
-                let h = (h_h - l_h) * color_pt + l_h;
-                let s = (h_s - l_s) * color_pt + l_s;
-                let l = (h_l - l_l) * color_pt + l_l;
-                let a = (h_a - l_a) * color_pt + l_a;
+        let space = self
+            .space
+            .unwrap_or_else(|| match (low.to_simple(), high.to_simple()) {
+                (Ok(SimpleColor::Hsla(..)), Ok(SimpleColor::Hsla(..))) => ColorSpace::Hsl,
+                _ => ColorSpace::Rgb,
+            });
+        match space {
+            ColorSpace::Hsl => {
+                let [l_h, l_s, l_l, l_a] = low.to_hsla().ok()?;
+                let [h_h, h_s, h_l, h_a] = high.to_hsla().ok()?;
+                let h = self.hue.interpolate(l_h * 360.0, h_h * 360.0, color_pt);
+                let s = lerp(l_s, h_s, color_pt);
+                let l = lerp(l_l, h_l, color_pt);
+                let a = lerp(l_a, h_a, color_pt);
 
                 Some(Color::Hsla {
-                    h: (h * 360.0).round() as u16,
+                    h: (h.round() as u16) % 360,
                     s: (s * 100.0).round() as u8,
                     l: (l * 100.0).round() as u8,
                     a: (a * 100.0).round() as u8,
                 })
             }
-            (SimpleColor::Rgba(l_r, l_g, l_b, l_a), SimpleColor::Rgba(h_r, h_g, h_b, h_a)) => {
-                let r = (h_r as f32 - l_r as f32) * color_pt + l_r as f32;
-                let g = (h_g as f32 - l_g as f32) * color_pt + l_g as f32;
-                let b = (h_b as f32 - l_b as f32) * color_pt + l_b as f32;
-                let a = (h_a as f32 - l_a as f32) * color_pt + l_a as f32;
-
-                Some(Color::Rgba {
-                    r: (r).round() as u8,
-                    g: (g).round() as u8,
-                    b: (b).round() as u8,
-                    a: (a).round() as u8,
+            ColorSpace::Rgb => {
+                let [l_r, l_g, l_b, l_a] = low.to_rgba().ok()?;
+                let [h_r, h_g, h_b, h_a] = high.to_rgba().ok()?;
+                let [r, g, b, a] = [(l_r, h_r), (l_g, h_g), (l_b, h_b), (l_a, h_a)]
+                    .map(|(low, high)| lerp(low as f32, high as f32, color_pt).round() as u8);
+
+                Some(Color::Rgba { r, g, b, a })
+            }
+            ColorSpace::Oklab => {
+                let [l_l, l_a, l_b, l_alpha] = low.to_oklab().ok()?;
+                let [h_l, h_a, h_b, h_alpha] = high.to_oklab().ok()?;
+
+                Some(Color::Oklab {
+                    l: BoundedFloat::clamped(lerp(l_l, h_l, color_pt)),
+                    a: BoundedFloat::clamped(lerp(l_a, h_a, color_pt)),
+                    b: BoundedFloat::clamped(lerp(l_b, h_b, color_pt)),
+                    alpha: BoundedFloat::clamped(lerp(l_alpha, h_alpha, color_pt)),
+                })
+            }
+            ColorSpace::Oklch => {
+                let [l_l, l_c, l_h, l_alpha] = low.to_oklch().ok()?;
+                let [h_l, h_c, h_h, h_alpha] = high.to_oklch().ok()?;
+
+                Some(Color::Oklch {
+                    l: BoundedFloat::clamped(lerp(l_l, h_l, color_pt)),
+                    c: BoundedFloat::clamped(lerp(l_c, h_c, color_pt)),
+                    h: BoundedFloat::clamped(self.hue.interpolate(l_h, h_h, color_pt)),
+                    alpha: BoundedFloat::clamped(lerp(l_alpha, h_alpha, color_pt)),
                 })
             }
-            _ => None,
         }
     }
 
@@ -127,6 +244,8 @@ impl FromIterator<(BoundedFloat<0, 1>, Color)> for Gradient {
     fn from_iter<T: IntoIterator<Item = (BoundedFloat<0, 1>, Color)>>(iter: T) -> Self {
         Self {
             points: iter.into_iter().collect(),
+            space: None,
+            hue: HueInterpolation::default(),
         }
     }
 }
@@ -150,7 +269,7 @@ impl IntoIterator for Gradient {
 }
 #[cfg(test)]
 mod tests {
-    use crate::theme::gradient::Gradient;
+    use crate::theme::gradient::{ColorSpace, Gradient, HueInterpolation};
     use crate::theme::Color;
     use crate::utils::bounded_float::BoundedFloat;
 
@@ -176,4 +295,34 @@ mod tests {
             gradient.print_gradient();
         }
     }
+
+    #[test]
+    fn hsl_hue_wraps_around() {
+        let gradient = Gradient::new(Color::hsl(350, 100, 50), Color::hsl(10, 100, 50));
+        let Color::Hsla { h, .. } = gradient.get(BoundedFloat::new(0.5).unwrap()) else {
+            panic!("should interpolate in hsl");
+        };
+        assert_eq!(h, 0);
+
+        let gradient = Gradient::new(Color::hsl(350, 100, 50), Color::hsl(10, 100, 50))
+            .with_space(ColorSpace::Hsl)
+            .with_hue_interpolation(HueInterpolation::Longer);
+        let Color::Hsla { h, .. } = gradient.get(BoundedFloat::new(0.5).unwrap()) else {
+            panic!("should interpolate in hsl");
+        };
+        assert_eq!(h, 180);
+    }
+
+    #[test]
+    fn oklch_gradient() {
+        let gradient = Gradient::new(Color::rgb(0, 0, 255), Color::rgb(255, 255, 255))
+            .with_space(ColorSpace::Oklch);
+        let middle = gradient.get(BoundedFloat::new(0.5).unwrap());
+        let Color::Oklch { l, .. } = middle else {
+            panic!("should interpolate in oklch");
+        };
+        let [low_l, ..] = Color::rgb(0, 0, 255).to_oklch().unwrap();
+        assert!((*l - (low_l + 1.0) / 2.0).abs() < 0.01);
+        gradient.print_gradient();
+    }
 }
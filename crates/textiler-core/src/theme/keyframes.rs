@@ -0,0 +1,33 @@
+//! Keyframes for css animations
+
+use serde::Serialize;
+
+use crate::Sx;
+
+/// The frames of a css animation, keyed by frame selectors like `from`, `50%` and `to`.
+///
+/// Keyframes are emitted as an `@keyframes` rule. When used as an sx value, such as
+/// `animationName: keyframes! { ... }`, the rule is named after a hash of its content.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Keyframes {
+    frames: Sx,
+}
+
+impl Keyframes {
+    /// Creates keyframes from an sx, where every key is a frame selector
+    pub fn new(frames: Sx) -> Self {
+        Self { frames }
+    }
+
+    /// Gets the frames of these keyframes
+    pub fn frames(&self) -> &Sx {
+        &self.frames
+    }
+}
+
+impl From<Sx> for Keyframes {
+    fn from(frames: Sx) -> Self {
+        Self::new(frames)
+    }
+}
@@ -1,6 +1,7 @@
 //! Contains palette
 
 use indexmap::IndexMap;
+use serde::Serialize;
 use std::borrow::Borrow;
 use std::hash::Hash;
 
@@ -8,8 +9,9 @@ use crate::theme::theme_mode::ThemeMode;
 use crate::theme::Color;
 
 /// A palette contains an assortment of colors
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct Palette {
+    #[serde(rename = "selectors")]
     selector_to_colors: IndexMap<String, ColorByMode>,
 }
 
@@ -35,6 +37,60 @@ impl Palette {
         self.selector_to_colors.keys().map(|s| &**s)
     }
 
+    /// Creates a new palette containing this palette's selectors, with the selectors of `overrides`
+    /// replacing any that are defined in both.
+    pub fn extend(&self, overrides: &Palette) -> Palette {
+        let mut palette = self.clone();
+        for (selector, color) in &overrides.selector_to_colors {
+            let _ = palette
+                .selector_to_colors
+                .insert(selector.clone(), color.clone());
+        }
+        palette
+    }
+
+    /// Replaces the color of an existing selector for only the given modes. Returns `false` if the
+    /// selector isn't present in this palette.
+    pub(crate) fn set_mode_colors(
+        &mut self,
+        key: &str,
+        dark: Option<Color>,
+        light: Option<Color>,
+    ) -> bool {
+        let Some(by_mode) = self.selector_to_colors.get_mut(key) else {
+            return false;
+        };
+        let (old_dark, old_light) = match by_mode {
+            ColorByMode::Constant(c) => (c.clone(), c.clone()),
+            ColorByMode::ModeBased { dark, light } => (dark.clone(), light.clone()),
+        };
+        *by_mode = ColorByMode::ModeBased {
+            dark: dark.unwrap_or(old_dark),
+            light: light.unwrap_or(old_light),
+        };
+        true
+    }
+
+    /// Creates a copy of this palette with every color mapped
+    pub(crate) fn map_colors<F: Fn(&Color) -> Color>(&self, map: F) -> Self {
+        Self {
+            selector_to_colors: self
+                .selector_to_colors
+                .iter()
+                .map(|(selector, color)| {
+                    let color = match color {
+                        ColorByMode::Constant(c) => ColorByMode::Constant(map(c)),
+                        ColorByMode::ModeBased { dark, light } => ColorByMode::ModeBased {
+                            dark: map(dark),
+                            light: map(light),
+                        },
+                    };
+                    (selector.clone(), color)
+                })
+                .collect(),
+        }
+    }
+
     pub fn select<Q: Eq + Hash + ?Sized>(&self, selector: &Q, mode: &ThemeMode) -> Option<&Color>
     where
         String: Borrow<Q>,
@@ -53,7 +109,8 @@ impl Palette {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
 enum ColorByMode {
     Constant(Color),
     ModeBased { dark: Color, light: Color },
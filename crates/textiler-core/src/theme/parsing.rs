@@ -1,16 +1,20 @@
-//! Allows loading of themes from json files
+//! Allows loading of themes from json files, and writing them back
 
+use std::collections::BTreeMap;
 use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
 use bigdecimal::{BigDecimal, FromPrimitive};
 
 use indexmap::IndexMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::theme::gradient::Gradient;
+use crate::theme::gradient::{ColorSpace, Gradient, HueInterpolation};
+use crate::theme::keyframes::Keyframes;
 use crate::theme::palette::Palette;
+use crate::theme::scale::Scale;
 use crate::theme::sx::SxValue;
-use crate::theme::typography::TypographyLevel;
+use crate::theme::typography::{TypographyLevel, TypographyScale};
+use crate::theme::variants;
 use crate::theme::{Color, Theme, PALETTE_SELECTOR_REGEX};
 use crate::utils::bounded_float::BoundedFloat;
 use crate::{sx, Sx};
@@ -18,12 +22,68 @@ use crate::{sx, Sx};
 /// Parses a theme from a reader
 pub fn from_reader<R: Read>(reader: R) -> Result<Theme, io::Error> {
     let json: ThemeJson = serde_json::from_reader(reader)?;
-    Ok(from_theme_json(json))
+    from_theme_json(json)
 }
 
 pub fn from_str(reader: &str) -> Result<Theme, io::Error> {
     let json: ThemeJson = serde_json::from_str(reader)?;
-    Ok(from_theme_json(json))
+    from_theme_json(json)
+}
+
+/// Writes a theme as pretty printed json that [`from_str`] reads back unchanged
+pub fn to_string(theme: &Theme) -> Result<String, io::Error> {
+    Ok(serde_json::to_string_pretty(theme)?)
+}
+
+/// Writes a theme as json to a writer
+pub fn to_writer<W: Write>(writer: W, theme: &Theme) -> Result<(), io::Error> {
+    Ok(serde_json::to_writer_pretty(writer, theme)?)
+}
+
+impl Serialize for Theme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ThemeJsonRef {
+            prefix: &self.prefix,
+            breakpoints: self
+                .breakpoints()
+                .points()
+                .into_iter()
+                .map(|bp| (bp.abbrev(), bp.width()))
+                .collect(),
+            palettes: self
+                .palettes()
+                .map(|(name, palette)| {
+                    (name, palette.map_colors(|color| tokenize_color(color, self)))
+                })
+                .collect(),
+            typography: self.typography(),
+            spacing: self.spacing_unit(),
+            radius: self.radius(),
+            shadow: self.shadow(),
+            z_index: self.z_index(),
+            animations: self.animations(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Inverse of [`adjust_color`], turns palette css variables back into `palette.selector` form
+fn tokenize_color(color: &Color, theme: &Theme) -> Color {
+    match color {
+        Color::Var { var, fallback } => Color::Var {
+            var: theme
+                .find_palette_var(var)
+                .map(|(palette, selector)| format!("{palette}.{selector}"))
+                .unwrap_or_else(|| var.clone()),
+            fallback: fallback
+                .as_ref()
+                .map(|fallback| Box::new(tokenize_color(fallback, theme))),
+        },
+        color => color.clone(),
+    }
 }
 
 fn adjust_color(color: Color, theme: &Theme) -> Color {
@@ -43,38 +103,91 @@ fn adjust_color(color: Color, theme: &Theme) -> Color {
         color => color,
     }
 }
-fn from_theme_json(json: ThemeJson) -> Theme {
+fn from_theme_json(json: ThemeJson) -> Result<Theme, io::Error> {
     trace!("json: {:#?}", json);
-    let mut theme = json
-        .prefix
-        .map(|prefix| Theme::with_prefix(prefix))
-        .unwrap_or_else(Theme::new);
+    let base = match json.extends.as_deref() {
+        None => None,
+        Some("default") => Some(Theme::default()),
+        Some(other) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("can not extend unknown theme {other:?}"),
+            ))
+        }
+    };
+    let mut theme = match (base, json.prefix) {
+        (Some(base), Some(prefix)) => base.with_renamed_prefix(&prefix),
+        (Some(base), None) => base,
+        (None, Some(prefix)) => Theme::with_prefix(prefix),
+        (None, None) => Theme::new(),
+    };
 
-    if let Some(typography_scale) = json.typography {
-        for (level, scale) in typography_scale.levels {
-            theme.typography_mut().insert(level, scale.to_sx().into());
+    if let Some(breakpoints) = json.breakpoints {
+        for (abbrev, width) in breakpoints {
+            theme.breakpoints_mut().set(&abbrev, width);
         }
     }
 
+    if let Some(spacing) = json.spacing {
+        theme.set_spacing_unit(spacing);
+    }
+    if let Some(radius) = json.radius {
+        *theme.radius_mut() = theme.radius().extend(&radius);
+    }
+    if let Some(shadow) = json.shadow {
+        *theme.shadow_mut() = theme.shadow().extend(&shadow);
+    }
+    if let Some(z_index) = json.z_index {
+        *theme.z_index_mut() = theme.z_index().extend(&z_index);
+    }
+
+    if let Some(animations) = json.animations {
+        let animations = animations
+            .iter()
+            .map(|(name, frames)| (name, Keyframes::new(frames.to_sx())))
+            .collect::<Scale<_>>();
+        *theme.animations_mut() = theme.animations().extend(&animations);
+    }
+
+    if let Some(typography_scale) = json.typography {
+        let overrides = TypographyScale::new(
+            typography_scale
+                .levels
+                .into_iter()
+                .map(|(level, scale)| (level, scale.to_sx().into())),
+        );
+        let extended = theme.typography().extend(&overrides);
+        *theme.typography_mut() = extended;
+    }
+
     for (palette_name, def) in json.palettes {
-        let mut palette = Palette::new();
+        let mut palette = theme.get_palette(&palette_name).cloned().unwrap_or_default();
         if let Some(GradientJson {
             points: gradient,
             mode,
+            hue,
         }) = def.gradient
         {
-            use GradientMode::*;
             let gradient: Gradient = match mode {
                 None => gradient,
-                Some(Hsl) => gradient
+                Some(space) => gradient
                     .into_iter()
-                    .map(|(pt, c)| (pt, c.to_hsla_color().expect("could not convert to hsla")))
-                    .collect(),
-                Some(Rgb) => gradient
-                    .into_iter()
-                    .map(|(pt, c)| (pt, c.to_rgba_color().expect("could not convert to rgba")))
-                    .collect(),
+                    .map(|(pt, c)| {
+                        let converted = space.convert(&c).map_err(|e| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!(
+                                    "gradient stop {c} of {palette_name} could not be converted \
+                                     to {space:?}: {e}"
+                                ),
+                            )
+                        })?;
+                        Ok((pt, converted))
+                    })
+                    .collect::<Result<Gradient, io::Error>>()?
+                    .with_space(space),
             };
+            let gradient = gradient.with_hue_interpolation(hue.unwrap_or_default());
             for i in 0..=10 {
                 let as_float = BoundedFloat::new(i as f32 / 10.0).expect("must be valid");
                 palette.insert_constant(&format!("{:03}", i * 10), gradient.get(as_float));
@@ -87,32 +200,66 @@ fn from_theme_json(json: ThemeJson) -> Theme {
                         let c = adjust_color(c, &theme);
                         palette.insert_constant(&selector, c);
                     }
-                    SelectorJson::DarkLight { dark, light } => {
+                    SelectorJson::DarkLight {
+                        dark: Some(dark),
+                        light: Some(light),
+                    } => {
                         let dark = adjust_color(dark, &theme);
                         let light = adjust_color(light, &theme);
                         palette.insert_by_mode(&selector, dark, light);
                     }
+                    SelectorJson::DarkLight { dark, light } => {
+                        let dark = dark.map(|dark| adjust_color(dark, &theme));
+                        let light = light.map(|light| adjust_color(light, &theme));
+                        if (dark.is_none() && light.is_none())
+                            || !palette.set_mode_colors(&selector, dark, light)
+                        {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!(
+                                    "{palette_name}.{selector} must define both dark and light colors \
+                                     unless it extends an existing selector"
+                                ),
+                            ));
+                        }
+                    }
                 }
             }
         }
 
         theme.insert_palette(palette_name, palette);
     }
-    theme
+    variants::validate(&theme).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(theme)
 }
 
 #[derive(Debug, Deserialize)]
 struct ThemeJson {
+    extends: Option<String>,
     prefix: Option<String>,
+    breakpoints: Option<IndexMap<String, u16>>,
     palettes: IndexMap<String, PaletteJson>,
     typography: Option<TypographyScaleJson>,
+    spacing: Option<String>,
+    radius: Option<Scale<String>>,
+    shadow: Option<Scale<String>>,
+    #[serde(rename = "zIndex")]
+    z_index: Option<Scale<i32>>,
+    animations: Option<IndexMap<String, SxJson>>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "lowercase")]
-enum GradientMode {
-    Hsl,
-    Rgb,
+#[derive(Serialize)]
+struct ThemeJsonRef<'a> {
+    prefix: &'a str,
+    breakpoints: IndexMap<&'a str, u16>,
+    palettes: BTreeMap<&'a str, Palette>,
+    typography: &'a TypographyScale,
+    spacing: &'a str,
+    radius: &'a Scale<String>,
+    shadow: &'a Scale<String>,
+    #[serde(rename = "zIndex")]
+    z_index: &'a Scale<i32>,
+    animations: &'a Scale<Keyframes>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -124,14 +271,18 @@ struct PaletteJson {
 #[derive(Debug, Deserialize)]
 struct GradientJson {
     points: Gradient,
-    mode: Option<GradientMode>,
+    mode: Option<ColorSpace>,
+    hue: Option<HueInterpolation>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum SelectorJson {
     Const(Color),
-    DarkLight { dark: Color, light: Color },
+    DarkLight {
+        dark: Option<Color>,
+        light: Option<Color>,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -150,14 +301,7 @@ impl SxJson {
     fn to_sx(&self) -> Sx {
         let mut sx = sx! {};
         for (key, value) in &self.mapping {
-            let sx_value = match value {
-                SxJsonValue::String(lit) => SxValue::CssLiteral(lit.clone()),
-                SxJsonValue::Nested(nested) => SxValue::Nested(nested.to_sx()),
-                SxJsonValue::Boolean(b) => SxValue::CssLiteral(b.to_string()),
-                SxJsonValue::Int(i) => SxValue::Integer((*i).into()),
-                SxJsonValue::Float(f) => SxValue::Float(BigDecimal::from_f32(*f).expect("must be representable")),
-            };
-            sx.insert(key.to_owned(), sx_value);
+            sx.insert(key.to_owned(), value.to_sx_value());
         }
         sx
     }
@@ -170,12 +314,40 @@ enum SxJsonValue {
     Boolean(bool),
     Int(i32),
     Float(f32),
+    Responsive(Vec<Option<SxJsonValue>>),
     Nested(SxJson),
 }
 
+impl SxJsonValue {
+    fn to_sx_value(&self) -> SxValue {
+        match self {
+            SxJsonValue::String(lit) => match PALETTE_SELECTOR_REGEX.captures(lit) {
+                Some(captures) => SxValue::ThemeToken {
+                    palette: captures["palette"].to_string(),
+                    selector: captures["selector"].to_string(),
+                },
+                None => SxValue::CssLiteral(lit.clone()),
+            },
+            SxJsonValue::Nested(nested) => SxValue::Nested(nested.to_sx()),
+            SxJsonValue::Responsive(values) => SxValue::Responsive(
+                values
+                    .iter()
+                    .map(|value| value.as_ref().map(SxJsonValue::to_sx_value))
+                    .collect(),
+            ),
+            SxJsonValue::Boolean(b) => SxValue::CssLiteral(b.to_string()),
+            SxJsonValue::Int(i) => SxValue::Integer((*i).into()),
+            SxJsonValue::Float(f) => SxValue::Float(BigDecimal::from_f32(*f).expect("must be representable")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::theme::parsing::from_str;
+    use crate::theme::parsing::{from_reader, from_str, to_string};
+    use crate::theme::sx::SxValue;
+    use crate::theme::typography::TypographyLevel;
+    use crate::theme::{Color, Theme, ThemeMode};
 
     #[test]
     fn parse_theme_json() {
@@ -184,4 +356,137 @@ mod tests {
 
         println!("parsed: {:#?}", parsed);
     }
+
+    #[test]
+    fn theme_json_round_trips() {
+        let json = include_str!("./theme.json");
+        let parsed = from_str(json).expect("could not parse");
+        let written = to_string(&parsed).expect("could not write");
+        let reparsed = from_reader(written.as_bytes()).expect("could not parse written theme");
+        assert_eq!(parsed, reparsed);
+        assert!(
+            written.contains(r#""var": "common.black""#),
+            "palette variables should be written as palette selectors: {written}"
+        );
+    }
+
+    #[test]
+    fn extend_default_theme() {
+        let json = r##"{
+            "extends": "default",
+            "prefix": "brand",
+            "breakpoints": {
+                "md": 800
+            },
+            "palettes": {
+                "text": {
+                    "selectors": {
+                        "primary": {
+                            "dark": "#ffeeee"
+                        }
+                    }
+                },
+                "brand": {
+                    "selectors": {
+                        "main": "#ff0000"
+                    }
+                }
+            },
+            "typography": {
+                "h1": {
+                    "fontWeight": 700
+                }
+            }
+        }"##;
+        let base = Theme::default();
+        let parsed = from_str(json).expect("could not parse");
+
+        assert_eq!(parsed.prefix, "brand");
+        assert_eq!(parsed.breakpoints().get("md").map(|bp| bp.width()), Some(800));
+        assert_eq!(
+            parsed.breakpoints().get("lg").map(|bp| bp.width()),
+            base.breakpoints().get("lg").map(|bp| bp.width())
+        );
+        assert!(parsed.get_palette("brand").is_some());
+        assert!(parsed.get_palette("neutral").is_some());
+
+        let text = parsed.get_palette("text").unwrap();
+        assert_eq!(
+            text.select("primary", &ThemeMode::Dark),
+            Some(&Color::CSSLiteral("#ffeeee".to_string()))
+        );
+        assert_eq!(
+            text.select("primary", &ThemeMode::Light),
+            Some(&Color::Var {
+                var: parsed.palette_var("neutral", "080"),
+                fallback: None
+            })
+        );
+
+        let h1 = parsed.typography().at(&TypographyLevel::H1).unwrap();
+        let base_h1 = base.typography().at(&TypographyLevel::H1).unwrap();
+        assert_eq!(h1["fontWeight"], SxValue::Integer(700.into()));
+        assert_eq!(h1["fontSize"], base_h1["fontSize"]);
+    }
+
+    #[test]
+    fn partial_selector_requires_base() {
+        let json = r##"{
+            "palettes": {
+                "text": {
+                    "selectors": {
+                        "primary": {
+                            "dark": "#ffeeee"
+                        }
+                    }
+                }
+            }
+        }"##;
+        assert!(from_str(json).is_err());
+        assert!(from_str(r#"{ "extends": "unknown", "palettes": {} }"#).is_err());
+    }
+
+    #[test]
+    fn unconvertible_gradient_stop_is_an_error() {
+        let json = r##"{
+            "palettes": {
+                "brand": {
+                    "gradient": {
+                        "points": {
+                            "0.0": "var(--brand-start)",
+                            "1.0": "#139513"
+                        },
+                        "mode": "oklch"
+                    }
+                }
+            }
+        }"##;
+        let error = from_str(json).expect_err("gradient stop can't be converted");
+        assert!(error.to_string().contains("gradient stop"), "{error}");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn parse_oklch_gradient() {
+        let json = r##"{
+            "palettes": {
+                "brand": {
+                    "gradient": {
+                        "points": {
+                            "0.0": "#e0ffe0",
+                            "1.0": "#139513"
+                        },
+                        "mode": "oklch",
+                        "hue": "longer"
+                    }
+                }
+            }
+        }"##;
+        let parsed = from_str(json).expect("could not parse");
+        let palette = parsed.get_palette("brand").expect("palette should exist");
+        let middle = palette
+            .select("050", &crate::theme::ThemeMode::Light)
+            .expect("gradient should create selectors");
+        assert!(matches!(middle, crate::theme::Color::Oklch { .. }));
+    }
 }
@@ -0,0 +1,68 @@
+//! Named scales of css values, such as border radii, shadows and z-index layers
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// The names of the scales in a theme that can be referenced with `scale.name` tokens
+pub(crate) const THEME_SCALES: [&str; 4] = ["radius", "shadow", "zIndex", "animation"];
+
+/// A named scale of values, referenced in sx through tokens like `radius.md`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Scale<T> {
+    values: IndexMap<String, T>,
+}
+
+impl<T> Default for Scale<T> {
+    fn default() -> Self {
+        Self {
+            values: IndexMap::new(),
+        }
+    }
+}
+
+impl<T> Scale<T> {
+    /// Creates a new, empty scale
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the value for a given name
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.values.get(name)
+    }
+
+    /// Inserts a value into this scale, replacing any previous value with the same name
+    pub fn insert(&mut self, name: impl AsRef<str>, value: T) {
+        let _ = self.values.insert(name.as_ref().to_string(), value);
+    }
+
+    /// Gets all the names and values of this scale, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+impl<T: Clone> Scale<T> {
+    /// Creates a new scale containing the values of this scale, with values in `overrides`
+    /// replacing any that share a name
+    pub fn extend(&self, overrides: &Scale<T>) -> Scale<T> {
+        let mut scale = self.clone();
+        for (name, value) in overrides.iter() {
+            scale.insert(name, value.clone());
+        }
+        scale
+    }
+}
+
+impl<K: AsRef<str>, T> FromIterator<(K, T)> for Scale<T> {
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
+        let mut scale = Scale::new();
+        for (name, value) in iter {
+            scale.insert(name, value);
+        }
+        scale
+    }
+}
@@ -11,25 +11,26 @@ use gloo::history::query::FromQuery;
 use heck::{ToKebabCase, ToLowerCamelCase, ToTrainCase};
 use indexmap::IndexMap;
 use indexmap::map::Entry;
-use serde::Deserialize;
+use serde::Serialize;
 use yew::html::ImplicitClone;
 
 pub use sx_value::*;
+pub use sx_value_parsing::ParseSxValueError;
+pub use sx_to_css::{SxToCssError, SxToCssErrorKind};
 use crate::style_manager::Css;
 
-use crate::system_props::{CssPropertyTranslator, SYSTEM_PROPERTIES};
 pub use crate::theme::sx;
 use crate::theme::sx::sx_to_css::sx_to_css;
 use crate::theme::Theme;
 use crate::theme::theme_mode::ThemeMode;
-use crate::utils::to_property;
 
 mod sx_to_css;
 mod sx_value;
 mod sx_value_parsing;
 
 /// Contains CSS definition with some customization
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize)]
+#[serde(transparent)]
 pub struct Sx {
     props: IndexMap<String, SxValue>,
 }
@@ -39,13 +40,10 @@ impl ImplicitClone for Sx {}
 // assert_impl_all!(Css: Display, ToString, Eq);
 
 impl Sx {
-    /// Sets a css property
+    /// Sets a css property. Keys are kept as written, with shorthands only translated by the
+    /// system properties of the theme the sx is converted with.
     pub fn insert<K: AsRef<str>, V: Into<SxValue>>(&mut self, key: K, value: V) {
-        let translated = SYSTEM_PROPERTIES.translate(key.as_ref());
-        let value = value.into();
-        for translated in translated {
-            self.props.insert(to_property(translated), value.clone());
-        }
+        self.props.insert(key.as_ref().to_string(), value.into());
     }
 
     /// Merges this Sx with another Sx. Uses the left's values for conflicting keys.
@@ -71,10 +69,34 @@ impl Sx {
         sx
     }
 
-    pub fn to_css(self, mode: &ThemeMode, theme: &Theme) -> Css {
-        let css = sx_to_css(self, mode, theme, None).expect("invalid sx");
+    /// Extends this Sx with another Sx. Unlike [`merge`](Self::merge), the other's values win
+    /// for conflicting keys. Nested values are extended recursively.
+    pub fn extend(self, overrides: Self) -> Self {
+        let mut sx = self;
 
-        css
+        for (prop, value) in overrides.props {
+            match sx.props.entry(prop) {
+                Entry::Occupied(mut occ) => {
+                    let value = match (occ.get_mut(), value) {
+                        (SxValue::Nested(old_sx), SxValue::Nested(sx)) => {
+                            SxValue::Nested(old_sx.clone().extend(sx))
+                        }
+                        (_, value) => value,
+                    };
+                    occ.insert(value);
+                }
+                Entry::Vacant(v) => {
+                    v.insert(value);
+                }
+            }
+        }
+
+        sx
+    }
+
+    /// Converts this sx into css, failing if any property can not be resolved against the theme
+    pub fn to_css(self, mode: &ThemeMode, theme: &Theme) -> Result<Css, crate::Error> {
+        sx_to_css(self, mode, theme, None)
     }
 
     /// Gets the properties set in this sx
@@ -101,6 +123,30 @@ macro_rules! sx {
     }
 }
 
+/// Creates [`Keyframes`](crate::theme::keyframes::Keyframes) as an sx value, using the same syntax
+/// as [`sx!`](crate::sx) with frame selectors as keys.
+///
+/// ```
+/// # use textiler_core::{keyframes, sx};
+/// let sx = sx! {
+///     animationName: keyframes! {
+///         from: { opacity: 0 },
+///         to: { opacity: 1 }
+///     },
+///     animationDuration: "200ms"
+/// };
+/// ```
+#[macro_export]
+macro_rules! keyframes {
+    (
+        $($json:tt)*
+    ) => {
+        $crate::theme::sx::SxValue::Keyframes($crate::theme::keyframes::Keyframes::new(
+            $crate::sx!($($json)*),
+        ))
+    }
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! sx_internal {
@@ -125,6 +171,11 @@ macro_rules! sx_internal {
 
 
 
+     // Next value is a responsive array.
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+       sx_internal!(@object $object [$($key)+] (sx_internal!(@array [] ($($array)*))) $($rest)*);
+    };
+
      // Next value is a map.
     (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
        sx_internal!(@object $object [$($key)+] (sx_internal!({$($map)*})) $($rest)*);
@@ -178,6 +229,28 @@ macro_rules! sx_internal {
     };
 
 
+    // TT parser for responsive arrays
+
+    // done
+    (@array [$($elems:expr,)*] ()) => {
+        SxValue::Responsive(vec![$($elems,)*])
+    };
+
+    // null skips a breakpoint
+    (@array [$($elems:expr,)*] (null $(, $($rest:tt)*)?)) => {
+        sx_internal!(@array [$($elems,)* None,] ($($($rest)*)?))
+    };
+
+    // Next element is followed by a comma
+    (@array [$($elems:expr,)*] ($next:expr , $($rest:tt)*)) => {
+        sx_internal!(@array [$($elems,)* Some(sx_internal!($next)),] ($($rest)*))
+    };
+
+    // Last element
+    (@array [$($elems:expr,)*] ($last:expr)) => {
+        sx_internal!(@array [$($elems,)* Some(sx_internal!($last)),] ())
+    };
+
     // main implementation
     ({}) => {
         $crate::Sx::default()
@@ -199,7 +272,10 @@ macro_rules! sx_internal {
     };
 
     ($expr:expr) => {
-        SxValue::try_from($expr).expect("could not create sxvalue")
+        match SxValue::try_from($expr) {
+            Ok(value) => value,
+            Err(err) => SxValue::invalid(stringify!($expr), err),
+        }
     };
 
 
@@ -242,6 +318,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extend_sx() {
+        let base = sx! {
+            "color": "text.primary",
+            "fontSize": "1rem",
+            "&:hover": {
+                "color": "text.secondary",
+                "padding": "2px"
+            }
+        };
+        let extended = base.extend(sx! {
+            "fontSize": "2rem",
+            "&:hover": {
+                "color": "text.tertiary",
+            }
+        });
+
+        assert_eq!(
+            extended["fontSize"],
+            SxValue::Dimension {
+                value: 2.into(),
+                unit: "rem".to_string(),
+            }
+        );
+        assert_eq!(
+            extended["color"],
+            SxValue::ThemeToken {
+                palette: "text".to_string(),
+                selector: "primary".to_string(),
+            }
+        );
+        let SxValue::Nested(hover) = &extended["&:hover"] else {
+            panic!("hover should still be nested");
+        };
+        assert_eq!(
+            hover["color"],
+            SxValue::ThemeToken {
+                palette: "text".to_string(),
+                selector: "tertiary".to_string(),
+            }
+        );
+        assert!(hover.properties().into_iter().any(|prop| prop == "padding"));
+    }
+
+    #[test]
+    fn callbacks_are_equal_across_renders() {
+        let render = || sx! { color: |_theme| "red" };
+        assert_eq!(render(), render());
+        assert_ne!(render(), sx! { color: |_theme| "red" });
+    }
+
     #[test]
     fn to_css() {
         let theme = Theme::default();
@@ -251,7 +378,9 @@ mod tests {
             color: "background.body"
         };
 
-        let style = sx.to_css(&ThemeMode::default(), &theme);
+        let style = sx
+            .to_css(&ThemeMode::default(), &theme)
+            .expect("could not create css");
         println!("style: {style:#?}");
     }
 
@@ -266,7 +395,9 @@ mod tests {
             }
         };
 
-        let style = sx.to_css(&ThemeMode::default(), &theme);
+        let style = sx
+            .to_css(&ThemeMode::default(), &theme)
+            .expect("could not create css");
         println!("style: {style:#?}");
     }
 
@@ -280,7 +411,9 @@ mod tests {
             }
         };
 
-        let style = sx.to_css(&ThemeMode::default(), &theme);
+        let style = sx
+            .to_css(&ThemeMode::default(), &theme)
+            .expect("could not create css");
         println!("style: {style:#?}");
     }
 }
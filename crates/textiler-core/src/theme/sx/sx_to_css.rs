@@ -4,10 +4,12 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::io::Write;
 
+use bigdecimal::Zero;
 use heck::ToKebabCase;
 
-use crate::system_props::{CssPropertyTranslator, TranslationUnit};
+use crate::system_props::{is_spacing_property, CssPropertyTranslator, TranslationUnit};
 use crate::theme::sx::sx_value::SxValue;
+use crate::theme::scale::THEME_SCALES;
 use crate::theme::theme_mode::ThemeMode;
 use crate::theme::Theme;
 use crate::{utils, Error, Sx};
@@ -22,7 +24,7 @@ pub fn sx_to_css<'a>(
     let mut stack = vec![];
     let option = base_query.into();
     stack.extend(option.clone().map(|c| c.into()));
-    let css = _sx_to_css(sx, mode, theme, &mut stack)?;
+    let css = _sx_to_css(sx, mode, theme, &mut stack, &mut vec![])?;
 
     let mut buffer = vec![];
 
@@ -31,6 +33,7 @@ pub fn sx_to_css<'a>(
         Some(base) => {
             let Css { declarations } = css;
             let rule = Rule {
+                at_rules: vec![],
                 query: base.to_string(),
                 block: declarations,
             };
@@ -46,8 +49,9 @@ fn _sx_to_css<'a>(
     mode: &ThemeMode,
     theme: &Theme,
     query_stack: &'a mut Vec<String>,
+    path: &'a mut Vec<String>,
 ) -> Result<Css, crate::Error> {
-    let declarations = sx_to_declarations(sx, mode, theme, query_stack)?;
+    let declarations = sx_to_declarations(sx, mode, theme, query_stack, path)?;
     Ok(Css { declarations })
 }
 
@@ -56,14 +60,45 @@ fn sx_to_declarations<'a>(
     mode: &'a ThemeMode,
     theme: &'a Theme,
     query_stack: &'a mut Vec<String>,
+    path: &'a mut Vec<String>,
 ) -> Result<Vec<Declaration>, Error> {
     let mut declarations = vec![];
     let query_stack = query_stack;
     for (key, value) in sx.props.iter() {
-        let declaration = property_to_declaration(&*key, value, mode, theme, query_stack)?;
+        path.push(key.to_string());
+        let declaration = property_to_declaration(&*key, value, mode, theme, query_stack, path)?;
+        path.pop();
         declarations.extend(declaration);
     }
-    Ok(declarations)
+    Ok(group_rules(declarations))
+}
+
+/// Merges rules with the same query and at-rules that follow each other, so responsive values
+/// at the same breakpoint share one media query. Properties set between them are printed before
+/// any rule either way, so only other rules keep them apart.
+fn group_rules(declarations: Vec<Declaration>) -> Vec<Declaration> {
+    let mut grouped: Vec<Declaration> = vec![];
+    let mut last_rule: Option<usize> = None;
+    for declaration in declarations {
+        match declaration {
+            Declaration::SetProperty { .. } => grouped.push(declaration),
+            Declaration::Rule(rule) => {
+                if let Some(Declaration::Rule(last)) = last_rule.map(|index| &mut grouped[index]) {
+                    if last.query == rule.query && last.at_rules == rule.at_rules {
+                        last.block.extend(rule.block);
+                        continue;
+                    }
+                }
+                last_rule = Some(grouped.len());
+                grouped.push(Declaration::Rule(rule));
+            }
+            Declaration::Keyframes { .. } => {
+                last_rule = None;
+                grouped.push(declaration);
+            }
+        }
+    }
+    grouped
 }
 
 fn property_to_declaration<'a, 'b: 'a>(
@@ -72,9 +107,10 @@ fn property_to_declaration<'a, 'b: 'a>(
     mode: &'a ThemeMode,
     theme: &'a Theme,
     query_stack: &'a mut Vec<String>,
+    path: &'a mut Vec<String>,
 ) -> Result<Vec<Declaration>, crate::Error> {
     let mut value = Cow::<'a, _>::Borrowed(value);
-    let translator = TranslationUnit::new(&theme.breakpoints);
+    let translator = TranslationUnit::new(theme);
     let key = translator.translate(key);
     let resolved: SxValue = loop {
         match value.as_ref() {
@@ -83,12 +119,31 @@ fn property_to_declaration<'a, 'b: 'a>(
                 ref palette,
                 ref selector,
             } => {
-                let def_palette = theme
-                    .get_palette(palette)
-                    .unwrap_or_else(|| panic!("no palette named {palette:?} found"));
-                let _ = def_palette
-                    .select(selector, mode)
-                    .unwrap_or_else(|| panic!("Could not find selector {selector:?} in palette"));
+                let token = format!("{palette}.{selector}");
+                let Some(def_palette) = theme.get_palette(palette) else {
+                    if let Some(var) = theme.scale_token(palette, selector) {
+                        break SxValue::CssLiteral(var);
+                    }
+                    let kind = if THEME_SCALES.contains(&palette.as_str()) {
+                        SxToCssErrorKind::UnknownSelector {
+                            palette: palette.clone(),
+                            selector: selector.clone(),
+                        }
+                    } else {
+                        SxToCssErrorKind::UnknownPalette(palette.clone())
+                    };
+                    return Err(SxToCssError::new(path, &token, kind).into());
+                };
+                let _ = def_palette.select(selector, mode).ok_or_else(|| {
+                    SxToCssError::new(
+                        path,
+                        &token,
+                        SxToCssErrorKind::UnknownSelector {
+                            palette: palette.clone(),
+                            selector: selector.clone(),
+                        },
+                    )
+                })?;
 
                 break SxValue::CssLiteral(format!(
                     "var({})",
@@ -102,56 +157,277 @@ fn property_to_declaration<'a, 'b: 'a>(
             } => {
                 break SxValue::CssLiteral(match fallback {
                     None => format!("var({})", theme.class_var(class, var)),
-                    Some(fallback) => format!(
-                        "var({}, {})",
-                        theme.class_var(class, var),
-                        fallback.clone().to_css().expect("fallback be direct css")
-                    ),
+                    Some(fallback) => {
+                        let fallback = fallback.clone().to_css().ok_or_else(|| {
+                            SxToCssError::new(
+                                path,
+                                &format!("{fallback:?}"),
+                                SxToCssErrorKind::InvalidFallback,
+                            )
+                        })?;
+                        format!("var({}, {})", theme.class_var(class, var), fallback)
+                    }
                 })
             }
+            SxValue::Invalid { value, reason } => {
+                return Err(SxToCssError::new(
+                    path,
+                    value,
+                    SxToCssErrorKind::InvalidValue(reason.clone()),
+                )
+                .into());
+            }
             other => break other.clone(),
         }
     };
     match resolved {
+        SxValue::Nested(ref nested) if key.iter().any(|key| key.starts_with("@keyframes")) => {
+            let mut emit = vec![];
+            for key in key {
+                let name = key.trim_start_matches("@keyframes").trim();
+                emit.push(keyframes_declaration(name.to_string(), nested, mode, theme, path)?);
+            }
+            Ok(emit)
+        }
+        SxValue::Keyframes(ref keyframes) => {
+            let mut frames = keyframes_declaration(String::new(), keyframes.frames(), mode, theme, path)?;
+            let name = format!("{}-kf-{:x}", theme.prefix, utils::stable_hash(frames.to_string()));
+            if let Declaration::Keyframes { name: ref mut generated, .. } = frames {
+                *generated = name.clone();
+            }
+            let mut emit = key
+                .into_iter()
+                .map(|key| Declaration::SetProperty {
+                    property: utils::to_property(key),
+                    value: name.clone(),
+                })
+                .collect::<Vec<_>>();
+            emit.push(frames);
+            Ok(emit)
+        }
+        SxValue::Nested(ref nested) if is_responsive(nested, theme) => {
+            // breakpoints apply from the smallest up, followed by ranges in the order written
+            let mut by_breakpoint = nested.props.iter().collect::<Vec<_>>();
+            by_breakpoint.sort_by_key(|(bp, _)| match theme.breakpoints.get(bp) {
+                Some(bp) => (false, bp.width()),
+                None => (true, 0),
+            });
+            let mut emit = vec![];
+            for (bp, value) in by_breakpoint {
+                path.push(bp.to_string());
+                emit.extend(responsive_declarations(
+                    &key,
+                    bp,
+                    value,
+                    mode,
+                    theme,
+                    &translator,
+                    query_stack,
+                    path,
+                )?);
+                path.pop();
+            }
+            Ok(emit)
+        }
+        SxValue::Responsive(ref values) => {
+            let mut emit = vec![];
+            for (bp, value) in theme.breakpoints.points().into_iter().zip(values) {
+                let Some(value) = value else {
+                    continue;
+                };
+                path.push(bp.abbrev().to_string());
+                emit.extend(responsive_declarations(
+                    &key,
+                    bp.abbrev(),
+                    value,
+                    mode,
+                    theme,
+                    &translator,
+                    query_stack,
+                    path,
+                )?);
+                path.pop();
+            }
+            Ok(emit)
+        }
         SxValue::Nested(ref nested) => {
             let mut emit = vec![];
             for key in key {
                 query_stack.push(key.to_string());
                 trace!("query stack: {:?}", query_stack);
-                let key = query_stack
-                    .iter()
-                    .flat_map(|s| translator.translate(s))
-                    .fold(String::new(), |accum, next| {
-                        if next.starts_with(&['>', '~', '+', ',']) {
-                            format!("{}{}", accum, next)
-                        } else if next.starts_with("&") {
-                            format!("{}{}", accum, next.strip_prefix("&").unwrap())
-                        } else {
-                            format!("{} {}", accum, next)
-                        }
-                    });
-                let inner = sx_to_declarations(nested, mode, theme, query_stack)?;
+                let mut inner = sx_to_declarations(nested, mode, theme, query_stack, path)?;
+                if is_generated_content(&key) && !nested.props.contains_key("content") {
+                    inner.insert(
+                        0,
+                        Declaration::SetProperty {
+                            property: "content".to_string(),
+                            value: "\"\"".to_string(),
+                        },
+                    );
+                }
+                emit.push(Declaration::Rule(Rule::for_stack(query_stack, &translator, inner)));
                 query_stack.pop();
-                emit.push(Declaration::Rule(Rule {
-                    query: key.to_string(),
-                    block: inner,
-                }));
             }
             Ok(emit)
         }
-        simple => Ok(key
-            .into_iter()
-            .map(|key| Declaration::SetProperty {
-                property: utils::to_property(key),
-                value: simple
-                    .clone()
-                    .to_css()
-                    .expect("should always be able to convert to css now"),
-            })
-            .collect()),
+        simple => {
+            let value = simple.clone().to_css().ok_or_else(|| {
+                SxToCssError::new(path, &format!("{simple:?}"), SxToCssErrorKind::NotCss)
+            })?;
+            let spacing_factor = match &simple {
+                SxValue::Integer(factor) | SxValue::Float(factor) => Some(factor),
+                _ => None,
+            };
+            Ok(key
+                .into_iter()
+                .map(|key| {
+                    let property = utils::to_property(key);
+                    let value = match spacing_factor {
+                        Some(factor) if !factor.is_zero() && is_spacing_property(&property) => {
+                            theme.spacing(factor.clone())
+                        }
+                        _ => value.clone(),
+                    };
+                    Declaration::SetProperty { property, value }
+                })
+                .collect())
+        }
+    }
+}
+
+/// Creates an `@keyframes` rule from an sx whose keys are frame selectors and whose values are
+/// the properties of each frame
+fn keyframes_declaration(
+    name: String,
+    frames: &Sx,
+    mode: &ThemeMode,
+    theme: &Theme,
+    path: &mut Vec<String>,
+) -> Result<Declaration, crate::Error> {
+    let mut blocks = vec![];
+    for (selector, frame) in frames.props.iter() {
+        path.push(selector.to_string());
+        let SxValue::Nested(frame) = frame else {
+            return Err(SxToCssError::new(
+                path,
+                &format!("{frame:?}"),
+                SxToCssErrorKind::InvalidKeyframe,
+            )
+            .into());
+        };
+        let declarations = sx_to_declarations(frame, mode, theme, &mut vec![], path)?;
+        path.pop();
+        blocks.push((selector.to_string(), declarations));
+    }
+    Ok(Declaration::Keyframes {
+        name,
+        frames: blocks,
+    })
+}
+
+/// `::before` and `::after` pseudo elements are only rendered with a `content` property
+fn is_generated_content(selector: &str) -> bool {
+    selector.ends_with("::before") || selector.ends_with("::after")
+}
+
+/// A nested sx is a responsive value, like `p: { xs: "4px", md: "12px" }`, when all of its keys are
+/// breakpoint keys and none of its values are nested. Besides breakpoints, keys may be ranges like
+/// `md-down` or `sm-lg`.
+fn is_responsive(nested: &Sx, theme: &Theme) -> bool {
+    !nested.props.is_empty()
+        && nested.props.iter().all(|(key, value)| {
+            theme.breakpoints.query(key).is_some() && !matches!(value, SxValue::Nested(_))
+        })
+}
+
+/// Creates the declarations of a property at a single breakpoint of a responsive value. Values at
+/// a zero width breakpoint apply everywhere, so they aren't wrapped in a media query.
+#[allow(clippy::too_many_arguments)]
+fn responsive_declarations(
+    keys: &[Cow<'_, str>],
+    breakpoint: &str,
+    value: &SxValue,
+    mode: &ThemeMode,
+    theme: &Theme,
+    translator: &TranslationUnit<'_>,
+    query_stack: &mut Vec<String>,
+    path: &mut Vec<String>,
+) -> Result<Vec<Declaration>, crate::Error> {
+    let mut declarations = vec![];
+    for key in keys {
+        declarations.extend(property_to_declaration(
+            key,
+            value,
+            mode,
+            theme,
+            query_stack,
+            path,
+        )?);
+    }
+    if theme
+        .breakpoints
+        .get(breakpoint)
+        .is_some_and(|bp| bp.width() == 0)
+    {
+        return Ok(declarations);
+    }
+    query_stack.push(breakpoint.to_string());
+    let rule = Rule::for_stack(query_stack, translator, declarations);
+    query_stack.pop();
+    Ok(vec![Declaration::Rule(rule)])
+}
+
+/// An error occurred while converting an [`Sx`](Sx) into css, pointing at the offending property.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("invalid sx at `{path}`: {value:?} {kind}")]
+pub struct SxToCssError {
+    path: String,
+    value: String,
+    kind: SxToCssErrorKind,
+}
+
+impl SxToCssError {
+    fn new(path: &[String], value: &str, kind: SxToCssErrorKind) -> Self {
+        Self {
+            path: path.join(" > "),
+            value: value.to_string(),
+            kind,
+        }
+    }
+
+    /// The path of keys leading to the offending property, such as `.box > md > background-color`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The value that could not be converted
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The reason the value could not be converted
+    pub fn kind(&self) -> &SxToCssErrorKind {
+        &self.kind
     }
 }
 
+/// The reason an sx value could not be converted into css
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SxToCssErrorKind {
+    #[error("references unknown palette {0:?}")]
+    UnknownPalette(String),
+    #[error("references unknown selector {selector:?} in palette {palette:?}")]
+    UnknownSelector { palette: String, selector: String },
+    #[error("has a fallback that is not a plain css value")]
+    InvalidFallback,
+    #[error("can not be converted to css")]
+    NotCss,
+    #[error("could not be parsed: {0}")]
+    InvalidValue(String),
+    #[error("is not a keyframe, which must be a nested sx of properties")]
+    InvalidKeyframe,
+}
+
 #[derive(Debug)]
 struct Css {
     declarations: Vec<Declaration>,
@@ -168,10 +444,41 @@ impl Display for Css {
 
 #[derive(Debug)]
 struct Rule {
+    /// At-rules, like media queries, wrapping this rule from outermost to innermost
+    at_rules: Vec<String>,
     query: String,
     block: Vec<Declaration>,
 }
 
+impl Rule {
+    /// Creates a rule from the query stack, hoisting at-rules out of the selector so that a
+    /// breakpoint nested within a selector becomes `@media (...) { selector { ... } }`
+    fn for_stack(
+        query_stack: &[String],
+        translator: &TranslationUnit<'_>,
+        block: Vec<Declaration>,
+    ) -> Self {
+        let mut at_rules = vec![];
+        let mut query = String::new();
+        for next in query_stack.iter().flat_map(|s| translator.translate(s)) {
+            if next.starts_with('@') {
+                at_rules.push(next.to_string());
+            } else if next.starts_with(['>', '~', '+', ',']) {
+                query = format!("{}{}", query, next);
+            } else if let Some(stripped) = next.strip_prefix('&') {
+                query = format!("{}{}", query, stripped);
+            } else {
+                query = format!("{} {}", query, next);
+            }
+        }
+        Self {
+            at_rules,
+            query,
+            block,
+        }
+    }
+}
+
 impl Display for Rule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let props = self
@@ -180,16 +487,27 @@ impl Display for Rule {
             .filter(|f| matches!(f, Declaration::SetProperty { .. }))
             .collect::<Vec<_>>();
         if !props.is_empty() {
-            write!(f, "{query} {{", query = self.query)?;
+            for at_rule in &self.at_rules {
+                write!(f, "{at_rule} {{")?;
+            }
+            let query = self.query.trim();
+            if !query.is_empty() {
+                write!(f, "{query} {{")?;
+            }
             for dec in props {
                 write!(f, "{}", dec)?;
             }
-            write!(f, "}}")?;
+            if !query.is_empty() {
+                write!(f, "}}")?;
+            }
+            for _ in &self.at_rules {
+                write!(f, "}}")?;
+            }
         }
         for dec in self
             .block
             .iter()
-            .filter(|f| matches!(f, Declaration::Rule(_)))
+            .filter(|f| !matches!(f, Declaration::SetProperty { .. }))
         {
             write!(f, "{}", dec)?;
         }
@@ -201,6 +519,10 @@ impl Display for Rule {
 enum Declaration {
     SetProperty { property: String, value: String },
     Rule(Rule),
+    Keyframes {
+        name: String,
+        frames: Vec<(String, Vec<Declaration>)>,
+    },
 }
 
 impl Display for Declaration {
@@ -212,6 +534,17 @@ impl Display for Declaration {
             Declaration::Rule(rule) => {
                 write!(f, "{}", rule)
             }
+            Declaration::Keyframes { name, frames } => {
+                write!(f, "@keyframes {name} {{")?;
+                for (selector, declarations) in frames {
+                    write!(f, "{selector} {{")?;
+                    for dec in declarations {
+                        write!(f, "{}", dec)?;
+                    }
+                    write!(f, "}}")?;
+                }
+                write!(f, "}}")
+            }
         }
     }
 }
@@ -235,6 +568,293 @@ mod tests {
         println!("css: {:?}", css);
     }
 
+    #[test]
+    fn numeric_spacing_uses_theme_spacing() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { p: 2, marginX: 0.5, opacity: 1 },
+            &ThemeMode::Light,
+            &theme,
+            ".spaced",
+        )
+        .expect("could not create css");
+
+        assert!(css.contains(&format!("padding: {};", theme.spacing(2))), "{css}");
+        assert!(css.contains("margin-left: calc(0.5 * var(--textiler-spacing));"), "{css}");
+        assert!(css.contains("opacity: 1;"), "{css}");
+    }
+
+    #[test]
+    fn scale_tokens_resolve_to_vars() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { borderRadius: "radius.md", boxShadow: "shadow.lg", zIndex: "zIndex.modal" },
+            &ThemeMode::Light,
+            &theme,
+            ".scaled",
+        )
+        .expect("could not create css");
+
+        assert!(css.contains("border-radius: var(--textiler-radius-md);"), "{css}");
+        assert!(css.contains("box-shadow: var(--textiler-shadow-lg);"), "{css}");
+        assert!(css.contains("z-index: var(--textiler-z-index-modal);"), "{css}");
+
+        let err = sx_to_css(sx! { borderRadius: "radius.huge" }, &ThemeMode::Light, &theme, ".scaled")
+            .expect_err("unknown radius should fail");
+        let Error::SxToCssError(err) = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert!(matches!(err.kind(), SxToCssErrorKind::UnknownSelector { .. }));
+    }
+
+    #[test]
+    fn nested_breakpoints_hoist_media_queries() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { padding: "15px", md: { padding: "20px" } },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {padding: 15px;}@media (min-width: 768px) {.cls {padding: 20px;}}"
+        );
+    }
+
+    #[test]
+    fn theme_system_properties() {
+        let mut theme = Theme::default();
+        theme.system_properties_mut().insert("sz", ["width", "height"]);
+        let css = sx_to_css(sx! { sz: "10px", mt: 1 }, &ThemeMode::Light, &theme, ".cls")
+            .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {width: 10px;height: 10px;margin-top: calc(1 * var(--textiler-spacing));}"
+        );
+    }
+
+    #[test]
+    fn theme_overrides_default_shorthands() {
+        let mut theme = Theme::default();
+        theme.system_properties_mut().insert("p", ["paddingTop"]);
+        theme.system_properties_mut().remove("mx");
+        let css = sx_to_css(sx! { p: "4px", mx: "2px" }, &ThemeMode::Light, &theme, ".cls")
+            .expect("could not create css");
+        assert_eq!(css, ".cls {padding-top: 4px;mx: 2px;}");
+    }
+
+    #[test]
+    fn keyframes_are_named_by_content() {
+        let theme = Theme::default();
+        let fade = || {
+            crate::keyframes! {
+                from: { opacity: 0 },
+                "50%": { opacity: 0.5 },
+                to: { opacity: 1 }
+            }
+        };
+        let css = sx_to_css(
+            sx! { animationName: fade(), animationDuration: "1s" },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        let other = sx_to_css(sx! { animationName: fade() }, &ThemeMode::Light, &theme, ".other")
+            .expect("could not create css");
+
+        let name = css
+            .split("animation-name: ")
+            .nth(1)
+            .and_then(|rest| rest.split(';').next())
+            .expect("should set the animation name");
+        assert!(name.starts_with("textiler-kf-"), "{css}");
+        assert!(other.contains(name), "same keyframes should get the same name: {other}");
+        assert!(
+            css.ends_with(&format!(
+                "@keyframes {name} {{from {{opacity: 0;}}50% {{opacity: 0.5;}}to {{opacity: 1;}}}}"
+            )),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn animation_tokens() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { animationName: "animation.spin" },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(css, ".cls {animation-name: textiler-spin;}");
+    }
+
+    #[test]
+    fn pseudo_shorthands() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! {
+                _hover: { color: "red" },
+                _focusVisible: { outline: "none" },
+                _disabled: { opacity: 0.5 },
+                _before: { display: "block" },
+                _after: { content: "\"!\"" },
+            },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls:hover {color: red;}\
+             .cls:focus-visible {outline: none;}\
+             .cls:disabled {opacity: 0.5;}\
+             .cls[aria-disabled=true] {opacity: 0.5;}\
+             .cls::before {content: \"\";display: block;}\
+             .cls::after {content: \"!\";}"
+        );
+    }
+
+    #[test]
+    fn nested_at_rules_hoist() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! {
+                containerType: "inline-size",
+                containerName: "card",
+                ".title": {
+                    "@md/card": { fontSize: "2rem" },
+                    "@sm": { fontSize: "1.5rem" },
+                    "@supports (display: grid)": { display: "grid" },
+                    "@media (hover: hover)": {
+                        _hover: { color: "red" }
+                    }
+                }
+            },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {container-type: inline-size;container-name: card;}\
+             @container card (min-width: 768px) {.cls .title {font-size: 2rem;}}\
+             @container (min-width: 600px) {.cls .title {font-size: 1.5rem;}}\
+             @supports (display: grid) {.cls .title {display: grid;}}\
+             @media (hover: hover) {.cls .title:hover {color: red;}}"
+        );
+    }
+
+    #[test]
+    fn at_rules_nest_within_each_other() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! {
+                md: {
+                    "@supports (display: grid)": { display: "grid" }
+                }
+            },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            "@media (min-width: 768px) {@supports (display: grid) {.cls {display: grid;}}}"
+        );
+    }
+
+    #[test]
+    fn breakpoint_range_keys() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { "md-down": { p: "4px" }, "lg-only": { p: "8px" } },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            "@media (max-width: 991.98px) {.cls {padding: 4px;}}\
+             @media (min-width: 992px) and (max-width: 1199.98px) {.cls {padding: 8px;}}"
+        );
+    }
+
+    #[test]
+    fn responsive_object_values() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { p: { md: "12px", xs: "4px" } },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {padding: 4px;}@media (min-width: 768px) {.cls {padding: 12px;}}"
+        );
+    }
+
+    #[test]
+    fn responsive_range_values() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { p: { "md-down": "4px", lg: "12px" } },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            "@media (min-width: 992px) {.cls {padding: 12px;}}\
+             @media (max-width: 991.98px) {.cls {padding: 4px;}}"
+        );
+    }
+
+    #[test]
+    fn responsive_values_share_media_queries() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { p: ["4px", null, "12px"], m: { md: "2px" }, color: "red" },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {padding: 4px;color: red;}\
+             @media (min-width: 768px) {.cls {padding: 12px;margin: 2px;}}"
+        );
+    }
+
+    #[test]
+    fn responsive_array_values() {
+        let theme = Theme::default();
+        let css = sx_to_css(
+            sx! { pX: ["4px", null, "12px"], color: "red" },
+            &ThemeMode::Light,
+            &theme,
+            ".cls",
+        )
+        .expect("could not create css");
+        assert_eq!(
+            css,
+            ".cls {padding-left: 4px;padding-right: 4px;color: red;}\
+             @media (min-width: 768px) {.cls {padding-left: 12px;padding-right: 12px;}}"
+        );
+    }
+
     #[test]
     fn sx_to_nested_css_test() {
         let sx = sx! {
@@ -252,6 +872,64 @@ mod tests {
         println!("Css: {:#?}", css);
     }
 
+    #[test]
+    fn unknown_palette_reports_path() {
+        let sx = sx! {
+            ".box": {
+                md: {
+                    bgcolor: "brand.body"
+                }
+            }
+        };
+        let ref theme = Theme::default();
+        let ref mode = ThemeMode::Dark;
+
+        let Err(Error::SxToCssError(error)) = sx_to_css(sx, mode, theme, None) else {
+            panic!("should fail on an unknown palette");
+        };
+        assert_eq!(error.path(), ".box > md > bgcolor");
+        assert_eq!(error.value(), "brand.body");
+        assert_eq!(
+            error.kind(),
+            &SxToCssErrorKind::UnknownPalette("brand".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_selector_reports_path() {
+        let sx = sx! {
+            color: "text.tertiary"
+        };
+        let ref theme = Theme::default();
+        let ref mode = ThemeMode::Light;
+
+        let Err(Error::SxToCssError(error)) = sx_to_css(sx, mode, theme, None) else {
+            panic!("should fail on an unknown selector");
+        };
+        assert_eq!(error.path(), "color");
+        assert!(matches!(
+            error.kind(),
+            SxToCssErrorKind::UnknownSelector { .. }
+        ));
+    }
+
+    #[test]
+    fn invalid_value_reports_path() {
+        let sx = sx! {
+            "div": {
+                color: "#zz"
+            }
+        };
+        let ref theme = Theme::default();
+        let ref mode = ThemeMode::Light;
+
+        let Err(Error::SxToCssError(error)) = sx_to_css(sx, mode, theme, None) else {
+            panic!("should fail on an unparsable value");
+        };
+        assert_eq!(error.path(), "div > color");
+        assert!(matches!(error.kind(), SxToCssErrorKind::InvalidValue(_)));
+    }
+
     #[test]
     fn format_properties() {
         assert_eq!(to_property("backgroundColor"), "background-color");
@@ -1,9 +1,13 @@
+use std::any::TypeId;
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use serde::ser::Error as _;
+use serde::{Serialize, Serializer};
 
 use crate::theme::sx::sx_value_parsing::{parse_sx_value, ParseSxValueError};
+use crate::theme::keyframes::Keyframes;
 use crate::theme::{Color, Theme, PALETTE_SELECTOR_REGEX};
 use crate::Sx;
 
@@ -35,6 +39,15 @@ pub enum SxValue {
     },
     Callback(FnSxValue),
     Nested(Sx),
+    /// A value per breakpoint, from the smallest breakpoint up. `None` skips a breakpoint.
+    Responsive(Vec<Option<SxValue>>),
+    /// Keyframes, emitted as an `@keyframes` rule with the value replaced by its generated name
+    Keyframes(Keyframes),
+    /// A value that could not be parsed, reported as an error when converted to css
+    Invalid {
+        value: String,
+        reason: String,
+    },
 }
 
 impl SxValue {
@@ -46,6 +59,14 @@ impl SxValue {
         }
     }
 
+    /// Creates an invalid value, deferring the error until this value is converted to css
+    pub fn invalid(value: impl AsRef<str>, reason: impl ToString) -> Self {
+        Self::Invalid {
+            value: value.as_ref().to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
     pub fn to_css(self) -> Option<String> {
         Some(match self {
             SxValue::Integer(i) => {
@@ -75,6 +96,41 @@ impl SxValue {
     }
 }
 
+impl Serialize for SxValue {
+    /// Numbers and nested values keep their json shape, theme tokens are written as
+    /// `palette.selector` and everything else as its css string.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SxValue::Integer(i) => match i.to_i64() {
+                Some(i) => serializer.serialize_i64(i),
+                None => serializer.collect_str(i),
+            },
+            SxValue::Float(f) => match f.to_f32() {
+                Some(f) => serializer.serialize_f32(f),
+                None => serializer.collect_str(f),
+            },
+            SxValue::ThemeToken { palette, selector } => {
+                serializer.collect_str(&format_args!("{palette}.{selector}"))
+            }
+            SxValue::Nested(sx) => sx.serialize(serializer),
+            SxValue::Responsive(values) => values.serialize(serializer),
+            SxValue::Keyframes(keyframes) => keyframes.serialize(serializer),
+            SxValue::ClassVar { .. } | SxValue::Callback(_) | SxValue::Invalid { .. } => Err(
+                S::Error::custom(format!("sx value can not be serialized: {self:?}")),
+            ),
+            other => serializer.serialize_str(
+                &other
+                    .clone()
+                    .to_css()
+                    .expect("all remaining values have a css representation"),
+            ),
+        }
+    }
+}
+
 impl From<i32> for SxValue {
     fn from(value: i32) -> Self {
         Self::Integer(value.into())
@@ -97,11 +153,25 @@ impl From<&str> for SxValue {
         } else if quoted_str.contains(char::is_whitespace) {
             SxValue::CssLiteral(quoted_str.to_string())
         } else {
-            quoted_str.parse().unwrap()
+            quoted_str
+                .parse()
+                .unwrap_or_else(|e| SxValue::invalid(quoted_str, e))
         }
     }
 }
 
+impl<V: Into<SxValue>> From<Vec<Option<V>>> for SxValue {
+    fn from(values: Vec<Option<V>>) -> Self {
+        Self::Responsive(values.into_iter().map(|v| v.map(Into::into)).collect())
+    }
+}
+
+impl From<Keyframes> for SxValue {
+    fn from(value: Keyframes) -> Self {
+        Self::Keyframes(value)
+    }
+}
+
 impl From<Sx> for SxValue {
     fn from(value: Sx) -> Self {
         Self::Nested(value)
@@ -119,13 +189,17 @@ impl FromStr for SxValue {
 /// An sx value derived from a function
 #[derive(Clone)]
 pub struct FnSxValue {
-    id: u64,
+    /// The closure's type, which is unique to where the closure was written
+    type_id: TypeId,
+    /// Closures without captures always return the same values, so they're equal by type alone
+    stateless: bool,
     callback: Arc<Mutex<dyn Fn(&Theme) -> SxValue + Send>>,
 }
 
 impl PartialEq for FnSxValue {
     fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+        self.type_id == other.type_id
+            && (self.stateless || Arc::ptr_eq(&self.callback, &other.callback))
     }
 }
 
@@ -135,7 +209,8 @@ impl FnSxValue {
         R: Into<SxValue>,
     {
         Self {
-            id: rand::random(),
+            type_id: TypeId::of::<F>(),
+            stateless: std::mem::size_of::<F>() == 0,
             callback: Arc::new(Mutex::new(move |theme: &Theme| (callback)(theme).into())),
         }
     }
@@ -1,7 +1,12 @@
 use dark_light::Mode;
+use serde::{Deserialize, Serialize};
+
+/// The attribute of `<html>` that selects the mode of the [`baseline`](crate::theme::baseline::baseline)
+pub const DATA_MODE_ATTRIBUTE: &str = "data-mode";
 
 /// The theme kind
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ThemeMode {
     /// Dark mode
     Dark,
@@ -25,4 +30,52 @@ impl ThemeMode {
             other => other,
         }
     }
+
+    /// Gets the value of the [`data-mode`](DATA_MODE_ATTRIBUTE) attribute for this mode
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "dark",
+            ThemeMode::Light => "light",
+            ThemeMode::System => "system",
+        }
+    }
+
+    /// Creates an inline script for server rendered pages, which sets the
+    /// [`data-mode`](DATA_MODE_ATTRIBUTE) attribute from the mode stored under `storage_key` before
+    /// the page is painted, so it doesn't flash the wrong mode while loading. Put it in a `<script>`
+    /// element at the start of `<head>`.
+    pub fn init_script(storage_key: &str) -> String {
+        let key = serde_json::to_string(storage_key)
+            .expect("strings are always valid json")
+            .replace("</", r"<\/");
+        format!(
+            "(function(){{var m;try{{m=JSON.parse(localStorage.getItem({key}))}}catch(e){{}}\
+             document.documentElement.setAttribute(\"{DATA_MODE_ATTRIBUTE}\",\
+             m===\"dark\"||m===\"light\"?m:\"system\")}})();"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_as_lowercase() {
+        assert_eq!(
+            serde_json::to_string(&ThemeMode::Dark).expect("could not serialize"),
+            r#""dark""#
+        );
+        assert_eq!(
+            serde_json::from_str::<ThemeMode>(r#""system""#).expect("could not deserialize"),
+            ThemeMode::System
+        );
+    }
+
+    #[test]
+    fn init_script_reads_storage_key() {
+        let script = ThemeMode::init_script("my-app-mode");
+        assert!(script.contains(r#"localStorage.getItem("my-app-mode")"#), "{script}");
+        assert!(script.contains(r#"setAttribute("data-mode","#), "{script}");
+    }
 }
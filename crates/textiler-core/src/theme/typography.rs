@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 /// Typography provides
 use std::fmt::{Display, Formatter};
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use yew::html::IntoPropValue;
 
 use crate::style::Size;
@@ -27,12 +27,21 @@ impl<'de> Deserialize<'de> for TypographyLevel {
     where
         D: Deserializer<'de>,
     {
-        let as_string = <&'de str as Deserialize<'de>>::deserialize(deserializer)?;
-        let parsed = TypographyLevel::from(as_string);
+        let as_string = String::deserialize(deserializer)?;
+        let parsed = TypographyLevel::from(as_string.as_str());
         Ok(parsed)
     }
 }
 
+impl Serialize for TypographyLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 impl Display for TypographyLevel {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -141,6 +150,34 @@ impl TypographyScale {
     pub fn insert(&mut self, level: TypographyLevel, level_sx: LevelScale) {
         let _ = self.levels.insert(level, level_sx);
     }
+
+    /// Creates a new scale with the levels of `overrides` extending the levels of this scale. Levels
+    /// present in both are merged per property, using the values from `overrides` on conflicts.
+    pub fn extend(&self, overrides: &TypographyScale) -> TypographyScale {
+        let mut scale = self.clone();
+        for (level, level_scale) in &overrides.levels {
+            let sx = match scale.levels.remove(level) {
+                Some(base) => base.sx.extend(level_scale.sx()),
+                None => level_scale.sx(),
+            };
+            scale.insert(level.clone(), sx.into());
+        }
+        scale
+    }
+}
+
+impl Serialize for TypographyScale {
+    /// Serializes levels by name, in a stable order
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.levels
+            .iter()
+            .map(|(level, scale)| (level.to_string(), &scale.sx))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
 }
 
 impl<'a> IntoIterator for &'a TypographyScale {
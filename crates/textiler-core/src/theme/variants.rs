@@ -0,0 +1,165 @@
+//! Palette selector conventions used to style every [`Variant`] of a component color.
+//!
+//! A palette used as a component [`Color`] defines its variant colors as `{variant}{suffix}`
+//! selectors, like `outlinedBorder` or `softHoverBg`.
+
+use strum::IntoEnumIterator;
+
+use crate::style::{Color, Variant};
+use crate::theme::palette::Palette;
+use crate::theme::sx::SxValue;
+use crate::theme::Theme;
+use crate::{sx, Sx};
+
+/// The selector suffixes a palette must define for a variant
+pub fn required_suffixes(variant: &Variant) -> &'static [&'static str] {
+    match variant {
+        Variant::Plain => &["Color", "HoverBg", "ActiveBg", "DisabledColor"],
+        Variant::Outlined => &[
+            "Color",
+            "Border",
+            "HoverBg",
+            "ActiveBg",
+            "DisabledColor",
+            "DisabledBorder",
+        ],
+        Variant::Soft | Variant::Solid => &[
+            "Color",
+            "Bg",
+            "HoverBg",
+            "ActiveBg",
+            "DisabledColor",
+            "DisabledBg",
+        ],
+    }
+}
+
+/// Gets the name of a variant's selector, like `outlinedBorder`
+pub fn selector_name(variant: &Variant, suffix: &str) -> String {
+    format!("{variant}{suffix}")
+}
+
+/// Gets the names of the selectors required by every variant
+pub fn selector_names() -> impl Iterator<Item = String> {
+    Variant::iter().flat_map(|variant| {
+        required_suffixes(&variant)
+            .iter()
+            .map(move |suffix| selector_name(&variant, suffix))
+    })
+}
+
+/// Gets the variant selectors missing from a palette
+pub fn missing_selectors(palette: &Palette) -> Vec<String> {
+    selector_names()
+        .filter(|selector| palette.selectors().all(|defined| defined != selector))
+        .collect()
+}
+
+/// A palette used as a component color doesn't define every variant selector
+#[derive(Debug, thiserror::Error)]
+#[error("palette {palette:?} is missing the variant selectors {missing:?}")]
+pub struct MissingVariantSelectors {
+    pub palette: String,
+    pub missing: Vec<String>,
+}
+
+/// Checks that every palette of the theme named after a [`Color`] defines all variant selectors.
+/// Themes parsed from json are validated, while themes built in code can be checked with
+/// [`Theme::validate_variants`].
+pub fn validate(theme: &Theme) -> Result<(), MissingVariantSelectors> {
+    for color in Color::iter() {
+        let palette_name = color.to_string();
+        if let Some(palette) = theme.get_palette(&palette_name) {
+            let missing = missing_selectors(palette);
+            if !missing.is_empty() {
+                return Err(MissingVariantSelectors {
+                    palette: palette_name,
+                    missing,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Creates the styling of every variant for a palette, nested below the system class. Each
+/// variant is selected with `&[data-variant={variant}][data-color={palette}]`.
+pub fn variant_sx(palette: &str) -> Sx {
+    let mut emit = sx!();
+    for variant in Variant::iter() {
+        let token = |suffix: &str| SxValue::ThemeToken {
+            palette: palette.to_string(),
+            selector: selector_name(&variant, suffix),
+        };
+        let suffixes = required_suffixes(&variant);
+        let has = |suffix: &str| suffixes.contains(&suffix);
+
+        let mut style = sx! {
+            "color": token("Color"),
+        };
+        let mut disabled = sx! {
+            "color": token("DisabledColor"),
+        };
+        if has("Bg") {
+            style.insert("bgcolor", token("Bg"));
+            disabled.insert("bgcolor", token("DisabledBg"));
+        } else {
+            // variants without a background would otherwise keep the hover background
+            disabled.insert("bgcolor", "transparent");
+        }
+        if has("Border") {
+            style.insert("borderColor", token("Border"));
+            disabled.insert("borderColor", token("DisabledBorder"));
+        }
+        style.insert("&:hover", sx! { "bgcolor": token("HoverBg") });
+        style.insert("&:active", sx! { "bgcolor": token("ActiveBg") });
+        // after hover and active, so disabled components don't react to the pointer
+        style.insert("&[disabled]", disabled.clone());
+        style.insert("&[aria-disabled=true]", disabled);
+
+        emit.insert(format!("&[data-variant={variant}][data-color={palette}]"), style);
+    }
+    emit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_theme_defines_all_variants() {
+        let theme = Theme::default();
+        validate(&theme).expect("default theme should define every variant selector");
+    }
+
+    #[test]
+    fn reports_missing_selectors() {
+        let mut theme = Theme::new();
+        theme
+            .palette("primary")
+            .insert_constant("plainColor", crate::theme::Color::CSSLiteral("red".to_string()));
+        let error = validate(&theme).expect_err("primary should be incomplete");
+        assert_eq!(error.palette, "primary");
+        assert!(!error.missing.contains(&"plainColor".to_string()));
+        assert!(error.missing.contains(&"solidDisabledBg".to_string()));
+        assert!(theme.validate_variants().is_err());
+    }
+
+    #[test]
+    fn disabled_variants_without_background_are_transparent() {
+        let sx = variant_sx("primary");
+        for variant in [Variant::Plain, Variant::Outlined] {
+            let key = format!("&[data-variant={variant}][data-color=primary]");
+            let SxValue::Nested(style) = &sx[&*key] else {
+                panic!("variant should be nested");
+            };
+            let SxValue::Nested(disabled) = &style["&[aria-disabled=true]"] else {
+                panic!("disabled should be nested");
+            };
+            assert_eq!(
+                disabled["bgcolor"],
+                SxValue::CssLiteral("transparent".to_string())
+            );
+        }
+    }
+}
@@ -14,7 +14,11 @@ pub static CSS_SELECTOR_OPERATORS: &[char] = &['.', '+', '>', '~', '&', ','];
 
 pub fn to_property(key: impl AsRef<str>) -> String {
     let key = key.as_ref();
-    if (key.starts_with('[') && key.ends_with(']')) || key.starts_with(CSS_SELECTOR_OPERATORS) {
+    if (key.starts_with('[') && key.ends_with(']'))
+        || key.starts_with(CSS_SELECTOR_OPERATORS)
+        || key.starts_with('@')
+        || key.starts_with(|c: char| c.is_ascii_digit())
+    {
         key.to_string()
     } else {
         key.split_inclusive(CSS_SELECTOR_OPERATORS)
@@ -35,3 +39,14 @@ pub fn to_property(key: impl AsRef<str>) -> String {
             .collect::<String>()
     }
 }
+
+/// A hash that is stable across builds and platforms, for naming generated css
+pub fn stable_hash(value: impl AsRef<[u8]>) -> u64 {
+    // 64 bit FNV-1a
+    value
+        .as_ref()
+        .iter()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
@@ -75,6 +75,18 @@ impl<const LOW: i16, const HIGH: i16> BoundedFloat<LOW, HIGH> {
             None
         }
     }
+
+    /// Creates a bounded float value, clamping the given val into the range given. `NaN` becomes
+    /// the lower bound.
+    pub fn clamped(val: f32) -> Self {
+        if val.is_nan() {
+            Self::MIN
+        } else {
+            Self {
+                num: val.clamp(LOW as f32, HIGH as f32),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +106,13 @@ mod tests {
         BoundedFloat::<0, 1>::new(1.).expect("is valid");
     }
 
+    #[test]
+    fn bounded_floats_clamped() {
+        assert_eq!(*BoundedFloat::<0, 1>::clamped(1.5), 1.0);
+        assert_eq!(*BoundedFloat::<-1, 1>::clamped(-3.0), -1.0);
+        assert_eq!(*BoundedFloat::<0, 1>::clamped(f32::NAN), 0.0);
+    }
+
     #[test]
     fn bounded_float_rejects_nan() {
         assert!(BoundedFloat::<0, 1>::new(f32::NAN).is_none());
@@ -0,0 +1,18 @@
+//! Components used for user input
+
+mod button;
+mod button_group;
+mod form_control;
+mod icon_button;
+mod input;
+mod textarea;
+
+pub use button::{Button, ButtonProps, LoadingPosition};
+pub use button_group::{ButtonGroup, ButtonGroupProps, Orientation};
+pub use form_control::{
+    use_form_control, FormControl, FormControlContext, FormControlProps, FormHelperText,
+    FormHelperTextProps, FormLabel, FormLabelProps,
+};
+pub use icon_button::{IconButton, IconButtonProps};
+pub use input::{Input, InputProps};
+pub use textarea::{Textarea, TextareaProps};
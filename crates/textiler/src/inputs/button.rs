@@ -0,0 +1,351 @@
+//! The button
+
+use yew::html::IntoPropValue;
+use yew::{
+    classes, function_component, html, AttrValue, Callback, Children, Html, MouseEvent, Properties,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::system::{ColorProp, SizeProp, VariantProp};
+
+use crate::inputs::button_group::use_button_group;
+
+/// Where the loading indicator of a button is shown
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum LoadingPosition {
+    /// Replaces the content of the button, keeping its width
+    #[default]
+    Center,
+    /// Replaces the start decorator
+    Start,
+    /// Replaces the end decorator
+    End,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct ButtonProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    /// Defaults to the variant of the surrounding [`ButtonGroup`](crate::inputs::ButtonGroup),
+    /// or solid
+    #[prop_or_default]
+    pub variant: VariantProp,
+    /// Defaults to the color of the surrounding [`ButtonGroup`](crate::inputs::ButtonGroup), or
+    /// primary
+    #[prop_or_default]
+    pub color: ColorProp,
+    #[prop_or_default]
+    pub size: SizeProp,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Shows a loading indicator and disables the button
+    #[prop_or_default]
+    pub loading: bool,
+    #[prop_or_default]
+    pub loading_position: LoadingPosition,
+    /// Replaces the default spinner
+    #[prop_or_default]
+    pub loading_indicator: Option<Html>,
+    /// Shown before the content
+    #[prop_or_default]
+    pub start_decorator: Option<Html>,
+    /// Shown after the content
+    #[prop_or_default]
+    pub end_decorator: Option<Html>,
+    /// Takes the full width of its container
+    #[prop_or_default]
+    pub full_width: bool,
+    /// Renders the button as a link
+    #[prop_or_default]
+    pub href: Option<AttrValue>,
+    /// The type of the button, which doesn't submit forms unless set to `submit`. Unused by links.
+    #[prop_or_else(|| AttrValue::Static("button"))]
+    pub r#type: AttrValue,
+    #[prop_or_default]
+    pub onclick: Option<Callback<MouseEvent>>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn Button(props: &ButtonProps) -> Html {
+    let group = use_button_group();
+    let variant = props.variant.or(*group.variant).unwrap_or(Variant::Solid);
+    let color = match (&*props.color, &*group.color) {
+        (Some(_), _) => props.color.clone(),
+        (None, Some(_)) => group.color.clone(),
+        (None, None) => Color::Primary.into_prop_value(),
+    };
+    let size = props.size.or(*group.size).unwrap_or_default();
+    let disabled = props.disabled || props.loading || group.disabled;
+    let full_width = props.full_width;
+
+    let class_sx = use_sx(move |_, _| {
+        let mut sx = button_sx(size, variant);
+        if full_width {
+            sx.insert("width", "100%");
+        }
+        sx
+    });
+
+    let indicator = props
+        .loading_indicator
+        .clone()
+        .unwrap_or_else(|| html! { <Spinner /> });
+    let loading_at =
+        |position: LoadingPosition| props.loading && props.loading_position == position;
+    let decorator = |class: &'static str, decorator: &Option<Html>, position: LoadingPosition| {
+        let content = if loading_at(position) {
+            Some(indicator.clone())
+        } else {
+            decorator.clone()
+        };
+        content.map(|content| html! { <span class={class}>{content}</span> })
+    };
+
+    let content = if loading_at(LoadingPosition::Center) {
+        html! {
+            <>
+                <span class="button-loading-center">{indicator.clone()}</span>
+                <span class="button-content-hidden">{ for props.children.iter() }</span>
+            </>
+        }
+    } else {
+        html! { for props.children.iter() }
+    };
+
+    html! {
+        <StylingBox
+            component={button_component(&props.href)}
+            href={props.href.clone()}
+            r#type={props.href.is_none().then(|| props.r#type.clone())}
+            class={classes!("button", class_sx)}
+            sx={props.sx.clone()}
+            variant={variant}
+            {color}
+            {disabled}
+            onclick={(!disabled).then(|| props.onclick.clone()).flatten()}
+        >
+            { decorator("button-start-decorator", &props.start_decorator, LoadingPosition::Start) }
+            { content }
+            { decorator("button-end-decorator", &props.end_decorator, LoadingPosition::End) }
+        </StylingBox>
+    }
+}
+
+/// Buttons are rendered as links if they have a target
+pub(crate) fn button_component(href: &Option<AttrValue>) -> AttrValue {
+    match href {
+        Some(_) => AttrValue::Static("a"),
+        None => AttrValue::Static("button"),
+    }
+}
+
+/// Gets the minimum height, font size and inline padding of a button
+pub(crate) fn size_metrics(size: Size) -> (&'static str, &'static str, &'static str) {
+    match size {
+        Size::Xs => ("1.75rem", "0.75rem", "0.5rem"),
+        Size::Sm => ("2rem", "0.875rem", "0.75rem"),
+        Size::Md => ("2.25rem", "1rem", "1rem"),
+        Size::Lg => ("2.75rem", "1.125rem", "1.5rem"),
+        Size::Xl => ("3.25rem", "1.25rem", "2rem"),
+    }
+}
+
+/// The styling shared by all buttons. Colors are left to the variant styling of the baseline.
+pub(crate) fn button_sx(size: Size, variant: Variant) -> Sx {
+    let (min_height, font_size, padding_inline) = size_metrics(size);
+    let border_style = match variant {
+        Variant::Outlined => "solid",
+        _ => "none",
+    };
+    sx! {
+        "display": "inline-flex",
+        "alignItems": "center",
+        "justifyContent": "center",
+        "position": "relative",
+        "boxSizing": "border-box",
+        "gap": "0.5em",
+        "minHeight": min_height,
+        "paddingInline": padding_inline,
+        "paddingBlock": "0",
+        "fontFamily": "inherit",
+        "fontSize": font_size,
+        "fontWeight": "600",
+        "lineHeight": "1",
+        "textDecoration": "none",
+        "borderRadius": "radius.sm",
+        "borderWidth": "1px",
+        "borderStyle": border_style,
+        "cursor": "pointer",
+        "userSelect": "none",
+        "&[aria-disabled=true]": {
+            "cursor": "default",
+            "pointerEvents": "none",
+        },
+        "& > .button-start-decorator": {
+            "display": "inline-flex",
+        },
+        "& > .button-end-decorator": {
+            "display": "inline-flex",
+        },
+        "& > .button-loading-center": {
+            "position": "absolute",
+            "inset": "0",
+            "display": "inline-flex",
+            "alignItems": "center",
+            "justifyContent": "center",
+        },
+        "& > .button-content-hidden": {
+            "visibility": "hidden",
+        },
+    }
+}
+
+/// The default loading indicator, which spins in the current text color
+#[function_component]
+pub(crate) fn Spinner() -> Html {
+    let class_sx = use_sx(|_, _| {
+        sx! {
+            "display": "inline-block",
+            "boxSizing": "border-box",
+            "width": "1em",
+            "height": "1em",
+            "borderWidth": "2px",
+            "borderStyle": "solid",
+            "borderColor": "currentColor",
+            "borderRightColor": "transparent",
+            "borderRadius": "50%",
+            "animationName": "animation.spin",
+            "animationDuration": "0.8s",
+            "animationTimingFunction": "linear",
+            "animationIterationCount": "infinite",
+        }
+    });
+
+    html! {
+        <span class={classes!("spinner", class_sx)} role="progressbar" aria-label="loading" />
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yew::{function_component, html, Html, ServerRenderer};
+
+    use super::*;
+    use crate::inputs::ButtonGroup;
+
+    #[tokio::test]
+    async fn render_button() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <Button size="sm" start_decorator={html! { {"+"} }}>{"Add"}</Button>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains("<button"), "{rendered}");
+        assert!(rendered.contains(r#"type="button""#), "{rendered}");
+        assert!(rendered.contains(r#"data-variant="solid""#), "{rendered}");
+        assert!(rendered.contains(r#"data-color="primary""#), "{rendered}");
+        assert!(
+            rendered.contains(r#"<span class="button-start-decorator">+</span>Add"#),
+            "{rendered}"
+        );
+    }
+
+    #[tokio::test]
+    async fn render_link_button() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <Button href="/home" variant="plain">{"Home"}</Button>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains("<a "), "{rendered}");
+        assert!(rendered.contains(r#"href="/home""#), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn render_submit_button() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <Button r#type="submit">{"Send"}</Button>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains(r#"type="submit""#), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn disabled_link_button_has_no_target() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <Button href="/home" disabled=true>{"Home"}</Button>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains("<a "), "{rendered}");
+        assert!(!rendered.contains("href"), "{rendered}");
+        assert!(!rendered.contains("type="), "{rendered}");
+        assert!(rendered.contains(r#"tabindex="-1""#), "{rendered}");
+        assert!(rendered.contains(r#"aria-disabled="true""#), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn loading_disables_button() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <>
+                    <Button loading=true>{"Save"}</Button>
+                    <Button loading=true loading_position={LoadingPosition::End}>{"Save"}</Button>
+                </>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        let (center, end) = rendered.split_once("</button>").unwrap();
+        for button in [center, end] {
+            assert!(button.contains(r#"aria-disabled="true""#), "{button}");
+            assert!(button.contains(r#"role="progressbar""#), "{button}");
+        }
+        assert!(center.contains("button-content-hidden"), "{center}");
+        assert!(end.contains("button-end-decorator"), "{end}");
+    }
+
+    #[tokio::test]
+    async fn group_provides_defaults() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <ButtonGroup variant="outlined" color="neutral" disabled=true>
+                    <Button>{"One"}</Button>
+                    <Button color="success">{"Two"}</Button>
+                </ButtonGroup>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains(r#"role="group""#), "{rendered}");
+        assert_eq!(
+            rendered.matches(r#"data-variant="outlined""#).count(),
+            2,
+            "{rendered}"
+        );
+        assert!(rendered.contains(r#"data-color="neutral""#), "{rendered}");
+        assert!(rendered.contains(r#"data-color="success""#), "{rendered}");
+        assert_eq!(
+            rendered.matches(r#"aria-disabled="true""#).count(),
+            2,
+            "{rendered}"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Groups buttons together
+
+use yew::{
+    classes, function_component, hook, html, use_context, Children, ContextProvider, Html,
+    Properties,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::style_manager::StyleLayer;
+use textiler_core::system::{ColorProp, SizeProp, VariantProp};
+
+/// The direction buttons are laid out in
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+/// The defaults a button group gives to its buttons
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct ButtonGroupContext {
+    pub(crate) variant: VariantProp,
+    pub(crate) color: ColorProp,
+    pub(crate) size: SizeProp,
+    pub(crate) disabled: bool,
+}
+
+/// Gets the defaults of the surrounding button group, if any
+#[hook]
+pub(crate) fn use_button_group() -> ButtonGroupContext {
+    use_context::<ButtonGroupContext>().unwrap_or_default()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct ButtonGroupProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    #[prop_or_default]
+    pub orientation: Orientation,
+    /// The variant of buttons that don't set their own
+    #[prop_or_default]
+    pub variant: VariantProp,
+    /// The color of buttons that don't set their own
+    #[prop_or_default]
+    pub color: ColorProp,
+    /// The size of buttons that don't set their own
+    #[prop_or_default]
+    pub size: SizeProp,
+    /// Disables every button in the group
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn ButtonGroup(props: &ButtonGroupProps) -> Html {
+    let orientation = props.orientation;
+    let class_sx = use_sx(move |_, _| {
+        // buttons are joined by squaring the corners they share
+        match orientation {
+            Orientation::Horizontal => sx! {
+                "display": "inline-flex",
+                "flexDirection": "row",
+                "& > :not(:first-child)": {
+                    "borderTopLeftRadius": "0",
+                    "borderBottomLeftRadius": "0",
+                },
+                "& > :not(:last-child)": {
+                    "borderTopRightRadius": "0",
+                    "borderBottomRightRadius": "0",
+                },
+            },
+            Orientation::Vertical => sx! {
+                "display": "inline-flex",
+                "flexDirection": "column",
+                "& > :not(:first-child)": {
+                    "borderTopLeftRadius": "0",
+                    "borderTopRightRadius": "0",
+                },
+                "& > :not(:last-child)": {
+                    "borderBottomLeftRadius": "0",
+                    "borderBottomRightRadius": "0",
+                },
+            },
+        }
+    });
+    let user_sx = use_sx_in_layer(StyleLayer::User, |_, _| props.sx.clone());
+
+    let context = ButtonGroupContext {
+        variant: props.variant,
+        color: props.color.clone(),
+        size: props.size,
+        disabled: props.disabled,
+    };
+
+    html! {
+        <ContextProvider<ButtonGroupContext> {context}>
+            <div role="group" class={classes!("button-group", class_sx, user_sx)}>
+                { for props.children.iter() }
+            </div>
+        </ContextProvider<ButtonGroupContext>>
+    }
+}
@@ -0,0 +1,264 @@
+//! Form controls share their state with the label, input and helper text inside them
+
+use yew::{
+    classes, function_component, hook, html, use_context, use_effect_with, use_state_eq,
+    AttrValue, Children, ContextProvider, Html, Properties, UseStateSetter,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::style_manager::StyleLayer;
+use textiler_core::system::SizeProp;
+
+use crate::inputs::button::size_metrics;
+
+/// The state a form control shares with its children
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormControlContext {
+    /// The id of the input
+    pub id: AttrValue,
+    pub disabled: bool,
+    pub error: bool,
+    pub required: bool,
+    pub size: SizeProp,
+    helper_text: bool,
+    set_helper_text: UseStateSetter<bool>,
+}
+
+impl FormControlContext {
+    /// The id of the label
+    pub fn label_id(&self) -> String {
+        format!("{}-label", self.id)
+    }
+
+    /// The id of the helper text
+    pub fn helper_text_id(&self) -> String {
+        format!("{}-helper-text", self.id)
+    }
+
+    /// The value of the input's `aria-describedby`, which is the helper text once it's mounted
+    pub fn described_by(&self) -> Option<String> {
+        self.helper_text.then(|| self.helper_text_id())
+    }
+}
+
+/// Gets the surrounding form control, if any
+#[hook]
+pub fn use_form_control() -> Option<FormControlContext> {
+    use_context::<FormControlContext>()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct FormControlProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    /// The id of the input, which the ids of the label and helper text are derived from. It's
+    /// required so that server and client rendering agree on it.
+    pub id: AttrValue,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub error: bool,
+    #[prop_or_default]
+    pub required: bool,
+    #[prop_or_default]
+    pub size: SizeProp,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn FormControl(props: &FormControlProps) -> Html {
+    let helper_text = use_state_eq(|| false);
+    let class_sx = use_sx(|_, _| {
+        sx! {
+            "display": "flex",
+            "flexDirection": "column",
+            "gap": "0.375rem",
+        }
+    });
+    let user_sx = use_sx_in_layer(StyleLayer::User, |_, _| props.sx.clone());
+
+    let context = FormControlContext {
+        id: props.id.clone(),
+        disabled: props.disabled,
+        error: props.error,
+        required: props.required,
+        size: props.size,
+        helper_text: *helper_text,
+        set_helper_text: helper_text.setter(),
+    };
+
+    html! {
+        <ContextProvider<FormControlContext> {context}>
+            <div class={classes!("form-control", class_sx, user_sx)}>
+                { for props.children.iter() }
+            </div>
+        </ContextProvider<FormControlContext>>
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct FormLabelProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Labels the input of the surrounding form control, marking it if required
+#[function_component]
+pub fn FormLabel(props: &FormLabelProps) -> Html {
+    let control = use_form_control();
+    let size = control
+        .as_ref()
+        .and_then(|control| *control.size)
+        .unwrap_or_default();
+    let (error, disabled) = control
+        .as_ref()
+        .map(|control| (control.error, control.disabled))
+        .unwrap_or_default();
+    let class_sx = use_sx(move |_, _| {
+        let (_, font_size, _) = size_metrics(size);
+        let mut sx = sx! {
+            "display": "block",
+            "fontSize": font_size,
+            "fontWeight": "500",
+        };
+        if error {
+            sx.insert("color", "fatal.plainColor");
+        } else if disabled {
+            sx.insert("color", "neutral.plainDisabledColor");
+        }
+        sx
+    });
+    let user_sx = use_sx_in_layer(StyleLayer::User, |_, _| props.sx.clone());
+
+    let (id, html_for, required) = match &control {
+        Some(control) => (
+            Some(control.label_id()),
+            Some(control.id.clone()),
+            control.required,
+        ),
+        None => (None, None, false),
+    };
+
+    html! {
+        <label {id} for={html_for} class={classes!("form-label", class_sx, user_sx)}>
+            { for props.children.iter() }
+            if required {
+                <span class="form-label-asterisk" aria-hidden="true">{" *"}</span>
+            }
+        </label>
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct FormHelperTextProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Describes the input of the surrounding form control
+#[function_component]
+pub fn FormHelperText(props: &FormHelperTextProps) -> Html {
+    let control = use_form_control();
+    // registers with the form control while mounted, so the input is described by it
+    use_effect_with(
+        control.as_ref().map(|control| control.set_helper_text.clone()),
+        |set_helper_text| {
+            if let Some(set_helper_text) = set_helper_text {
+                set_helper_text.set(true);
+            }
+            let set_helper_text = set_helper_text.clone();
+            move || {
+                if let Some(set_helper_text) = set_helper_text {
+                    set_helper_text.set(false);
+                }
+            }
+        },
+    );
+    let size = control
+        .as_ref()
+        .and_then(|control| *control.size)
+        .unwrap_or_default();
+    let (error, disabled) = control
+        .as_ref()
+        .map(|control| (control.error, control.disabled))
+        .unwrap_or_default();
+    let class_sx = use_sx(move |_, _| {
+        let (_, font_size, _) = size_metrics(size);
+        let mut sx = sx! {
+            "display": "flex",
+            "alignItems": "center",
+            "fontSize": SxValue::CssLiteral(format!("calc({font_size} * 0.875)")),
+        };
+        if error {
+            sx.insert("color", "fatal.plainColor");
+        } else if disabled {
+            sx.insert("color", "neutral.plainDisabledColor");
+        }
+        sx
+    });
+    let user_sx = use_sx_in_layer(StyleLayer::User, |_, _| props.sx.clone());
+
+    let id = control.as_ref().map(|control| control.helper_text_id());
+
+    html! {
+        <div {id} class={classes!("form-helper-text", class_sx, user_sx)}>
+            { for props.children.iter() }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yew::{function_component, html, Html, ServerRenderer};
+
+    use super::*;
+    use crate::inputs::Input;
+
+    #[tokio::test]
+    async fn wires_label_and_input() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <FormControl id="email" required=true error=true>
+                    <FormLabel>{"Email"}</FormLabel>
+                    <Input />
+                    <FormHelperText>{"Enter a valid email"}</FormHelperText>
+                </FormControl>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains(r#"id="email-label""#), "{rendered}");
+        assert!(rendered.contains(r#"for="email""#), "{rendered}");
+        assert!(rendered.contains(r#"id="email""#), "{rendered}");
+        assert!(rendered.contains(r#"id="email-helper-text""#), "{rendered}");
+        // the helper text registers once mounted, which doesn't happen on the server
+        assert!(!rendered.contains("aria-describedby"), "{rendered}");
+        assert!(rendered.contains("form-label-asterisk"), "{rendered}");
+        assert!(rendered.contains(r#"aria-invalid="true""#), "{rendered}");
+        assert!(rendered.contains(" required"), "{rendered}");
+        assert!(rendered.contains(r#"data-color="fatal""#), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn no_description_without_helper_text() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <FormControl id="name">
+                    <FormLabel>{"Name"}</FormLabel>
+                    <Input />
+                </FormControl>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains(r#"id="name""#), "{rendered}");
+        assert!(!rendered.contains("aria-describedby"), "{rendered}");
+    }
+}
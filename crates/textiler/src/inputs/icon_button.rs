@@ -0,0 +1,153 @@
+//! A button containing only an icon
+
+use yew::html::IntoPropValue;
+use yew::{
+    classes, function_component, html, AttrValue, Callback, Children, Html, MouseEvent, Properties,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::system::{ColorProp, SizeProp, VariantProp};
+
+use crate::inputs::button::{button_component, button_sx, size_metrics, Spinner};
+use crate::inputs::button_group::use_button_group;
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct IconButtonProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    /// Defaults to the variant of the surrounding [`ButtonGroup`](crate::inputs::ButtonGroup),
+    /// or plain
+    #[prop_or_default]
+    pub variant: VariantProp,
+    /// Defaults to the color of the surrounding [`ButtonGroup`](crate::inputs::ButtonGroup), or
+    /// neutral
+    #[prop_or_default]
+    pub color: ColorProp,
+    #[prop_or_default]
+    pub size: SizeProp,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Replaces the icon with a loading indicator and disables the button
+    #[prop_or_default]
+    pub loading: bool,
+    /// Replaces the default spinner
+    #[prop_or_default]
+    pub loading_indicator: Option<Html>,
+    /// Renders the button as a link
+    #[prop_or_default]
+    pub href: Option<AttrValue>,
+    /// The type of the button, which doesn't submit forms unless set to `submit`. Unused by links.
+    #[prop_or_else(|| AttrValue::Static("button"))]
+    pub r#type: AttrValue,
+    #[prop_or_default]
+    pub onclick: Option<Callback<MouseEvent>>,
+    /// The icon
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn IconButton(props: &IconButtonProps) -> Html {
+    let group = use_button_group();
+    let variant = props.variant.or(*group.variant).unwrap_or(Variant::Plain);
+    let color = match (&*props.color, &*group.color) {
+        (Some(_), _) => props.color.clone(),
+        (None, Some(_)) => group.color.clone(),
+        (None, None) => Color::Neutral.into_prop_value(),
+    };
+    let size = props.size.or(*group.size).unwrap_or_default();
+    let disabled = props.disabled || props.loading || group.disabled;
+
+    let class_sx = use_sx(move |_, _| {
+        let (min_height, ..) = size_metrics(size);
+        button_sx(size, variant).extend(sx! {
+            "minWidth": min_height,
+            "paddingInline": "0.25rem",
+        })
+    });
+
+    let content = if props.loading {
+        props
+            .loading_indicator
+            .clone()
+            .unwrap_or_else(|| html! { <Spinner /> })
+    } else {
+        html! { for props.children.iter() }
+    };
+
+    html! {
+        <StylingBox
+            component={button_component(&props.href)}
+            href={props.href.clone()}
+            r#type={props.href.is_none().then(|| props.r#type.clone())}
+            class={classes!("icon-button", class_sx)}
+            sx={props.sx.clone()}
+            variant={variant}
+            {color}
+            {disabled}
+            onclick={(!disabled).then(|| props.onclick.clone()).flatten()}
+        >
+            { content }
+        </StylingBox>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use textiler_core::context::ThemeProvider;
+    use textiler_core::style_manager::StyleCollector;
+    use yew::{function_component, html, Html, ServerRenderer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn render_icon_button() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <>
+                    <IconButton>{"x"}</IconButton>
+                    <IconButton loading=true>{"x"}</IconButton>
+                </>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        let (idle, loading) = rendered.split_once("</button>").unwrap();
+        assert!(idle.contains(r#"data-variant="plain""#), "{idle}");
+        assert!(idle.contains(r#"type="button""#), "{idle}");
+        assert!(idle.ends_with(">x"), "{idle}");
+        assert!(loading.contains(r#"role="progressbar""#), "{loading}");
+        assert!(!loading.contains(">x<"), "{loading}");
+    }
+
+    #[tokio::test]
+    async fn icon_button_is_square() {
+        #[derive(PartialEq, Properties)]
+        struct TestProps {
+            collector: StyleCollector,
+        }
+
+        #[function_component]
+        fn Test(props: &TestProps) -> Html {
+            html! {
+                <ThemeProvider collector={props.collector.clone()}>
+                    <IconButton>{"x"}</IconButton>
+                </ThemeProvider>
+            }
+        }
+
+        let collector = StyleCollector::new();
+        let _ = ServerRenderer::<Test>::with_props({
+            let collector = collector.clone();
+            move || TestProps { collector }
+        })
+        .render()
+        .await;
+
+        let css = collector.css();
+        assert!(css.contains("padding-inline: 0.25rem;"), "{css}");
+        assert!(css.contains("min-width: 2.25rem;"), "{css}");
+        assert!(!css.contains("padding-inline: 1rem;"), "{css}");
+    }
+}
@@ -0,0 +1,296 @@
+//! A single line text input
+
+use web_sys::HtmlInputElement;
+use yew::html::IntoPropValue;
+use yew::{
+    classes, function_component, hook, html, use_state, AttrValue, Callback, Html, InputEvent,
+    Properties, TargetCast,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::system::{ColorProp, SizeProp, VariantProp};
+
+use crate::inputs::button::size_metrics;
+use crate::inputs::form_control::{use_form_control, FormControlContext};
+
+#[derive(Debug, Default, Clone, PartialEq, Properties)]
+pub struct InputProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    /// Defaults to outlined
+    #[prop_or_default]
+    pub variant: VariantProp,
+    /// Defaults to neutral, or fatal if the input has an error
+    #[prop_or_default]
+    pub color: ColorProp,
+    /// Defaults to the size of the surrounding [`FormControl`](crate::inputs::FormControl)
+    #[prop_or_default]
+    pub size: SizeProp,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub error: bool,
+    #[prop_or_default]
+    pub required: bool,
+    /// Defaults to the id of the surrounding [`FormControl`](crate::inputs::FormControl)
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    #[prop_or_default]
+    pub name: Option<AttrValue>,
+    #[prop_or_else(|| AttrValue::Static("text"))]
+    pub r#type: AttrValue,
+    #[prop_or_default]
+    pub placeholder: Option<AttrValue>,
+    /// Makes the input controlled, always showing this value
+    #[prop_or_default]
+    pub value: Option<AttrValue>,
+    /// The initial value of an uncontrolled input
+    #[prop_or_default]
+    pub default_value: Option<AttrValue>,
+    /// Called with the new value whenever it's edited
+    #[prop_or_default]
+    pub on_change: Option<Callback<String>>,
+    /// Shown before the input
+    #[prop_or_default]
+    pub start_decorator: Option<Html>,
+    /// Shown after the input
+    #[prop_or_default]
+    pub end_decorator: Option<Html>,
+    /// Takes the full width of its container
+    #[prop_or_default]
+    pub full_width: bool,
+}
+
+#[function_component]
+pub fn Input(props: &InputProps) -> Html {
+    let control = use_form_control();
+    let state = input_state(
+        &control,
+        InputStateProps {
+            variant: props.variant,
+            color: props.color.clone(),
+            size: props.size,
+            disabled: props.disabled,
+            error: props.error,
+            required: props.required,
+            id: props.id.clone(),
+        },
+    );
+    let (value, set_value) = use_controlled_value(
+        props.value.clone(),
+        props.default_value.clone(),
+        props.on_change.clone(),
+    );
+    let (size, variant) = (state.size, state.variant);
+    let full_width = props.full_width;
+    let class_sx = use_sx(move |_, _| {
+        let mut sx = input_sx(size, variant);
+        if full_width {
+            sx.insert("width", "100%");
+        }
+        sx
+    });
+    let oninput = Callback::from(move |e: InputEvent| {
+        set_value.emit(e.target_unchecked_into::<HtmlInputElement>().value())
+    });
+
+    html! {
+        <StylingBox
+            class={classes!("input", class_sx)}
+            sx={props.sx.clone()}
+            {variant}
+            color={state.color.clone()}
+            disabled={state.disabled}
+        >
+            if let Some(decorator) = props.start_decorator.clone() {
+                <span class="input-start-decorator">{decorator}</span>
+            }
+            <input
+                class="input-input"
+                id={state.id.clone()}
+                name={props.name.clone()}
+                type={props.r#type.clone()}
+                placeholder={props.placeholder.clone()}
+                value={value}
+                disabled={state.disabled}
+                required={state.required}
+                aria-invalid={state.error.then_some("true")}
+                aria-describedby={state.described_by.clone()}
+                {oninput}
+            />
+            if let Some(decorator) = props.end_decorator.clone() {
+                <span class="input-end-decorator">{decorator}</span>
+            }
+        </StylingBox>
+    }
+}
+
+/// The props an input shares with its surrounding form control
+pub(crate) struct InputStateProps {
+    pub(crate) variant: VariantProp,
+    pub(crate) color: ColorProp,
+    pub(crate) size: SizeProp,
+    pub(crate) disabled: bool,
+    pub(crate) error: bool,
+    pub(crate) required: bool,
+    pub(crate) id: Option<AttrValue>,
+}
+
+/// The state of an input, combining its own props with the surrounding form control
+pub(crate) struct InputState {
+    pub(crate) variant: Variant,
+    pub(crate) color: ColorProp,
+    pub(crate) size: Size,
+    pub(crate) disabled: bool,
+    pub(crate) error: bool,
+    pub(crate) required: bool,
+    pub(crate) id: Option<AttrValue>,
+    pub(crate) described_by: Option<String>,
+}
+
+/// Combines the props of an input with the surrounding form control. Errors make the input fatal
+/// unless it sets its own color.
+pub(crate) fn input_state(
+    control: &Option<FormControlContext>,
+    props: InputStateProps,
+) -> InputState {
+    let from_control =
+        |get: fn(&FormControlContext) -> bool| control.as_ref().map(get).unwrap_or_default();
+    let error = props.error || from_control(|control| control.error);
+    let color = match (&*props.color, error) {
+        (Some(_), _) => props.color,
+        (None, true) => Color::Fatal.into_prop_value(),
+        (None, false) => Color::Neutral.into_prop_value(),
+    };
+    InputState {
+        variant: props.variant.unwrap_or(Variant::Outlined),
+        color,
+        size: props
+            .size
+            .or_else(|| control.as_ref().and_then(|control| *control.size))
+            .unwrap_or_default(),
+        disabled: props.disabled || from_control(|control| control.disabled),
+        error,
+        required: props.required || from_control(|control| control.required),
+        id: props
+            .id
+            .or_else(|| control.as_ref().map(|control| control.id.clone())),
+        described_by: control.as_ref().and_then(|control| control.described_by()),
+    }
+}
+
+/// Tracks the value of an input. Controlled inputs always show `value`, while uncontrolled inputs
+/// start with `default_value` and keep their own edits. Edits are reported to `on_change` either
+/// way.
+#[hook]
+pub(crate) fn use_controlled_value(
+    value: Option<AttrValue>,
+    default_value: Option<AttrValue>,
+    on_change: Option<Callback<String>>,
+) -> (AttrValue, Callback<String>) {
+    let uncontrolled = use_state(|| default_value.unwrap_or_default());
+    let controlled = value.is_some();
+    let current = value.unwrap_or_else(|| (*uncontrolled).clone());
+    let set_value = Callback::from(move |value: String| {
+        if !controlled {
+            uncontrolled.set(AttrValue::from(value.clone()));
+        }
+        if let Some(on_change) = &on_change {
+            on_change.emit(value);
+        }
+    });
+    (current, set_value)
+}
+
+/// The styling shared by inputs. Colors are left to the variant styling of the baseline.
+pub(crate) fn input_sx(size: Size, variant: Variant) -> Sx {
+    let (min_height, font_size, padding_inline) = size_metrics(size);
+    let border_style = match variant {
+        Variant::Outlined => "solid",
+        _ => "none",
+    };
+    sx! {
+        "display": "inline-flex",
+        "alignItems": "center",
+        "boxSizing": "border-box",
+        "gap": "0.5em",
+        "minHeight": min_height,
+        "paddingInline": padding_inline,
+        "paddingBlock": "0",
+        "fontSize": font_size,
+        "borderRadius": "radius.sm",
+        "borderWidth": "1px",
+        "borderStyle": border_style,
+        "cursor": "text",
+        "&:focus-within": {
+            "outline": "2px solid currentColor",
+            "outlineOffset": "-1px",
+        },
+        "&[aria-disabled=true]": {
+            "cursor": "default",
+        },
+        "& > .input-start-decorator": {
+            "display": "inline-flex",
+        },
+        "& > .input-end-decorator": {
+            "display": "inline-flex",
+        },
+        "& > .input-input": {
+            "flex": "1",
+            "minWidth": "0",
+            "padding": "0",
+            "border": "none",
+            "outline": "none",
+            "background": "transparent",
+            "color": "inherit",
+            "font": "inherit",
+            "resize": "none",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yew::{function_component, html, Html, ServerRenderer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn render_input() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <>
+                    <Input value="hello" placeholder="Name" start_decorator={html! { {"@"} }} />
+                    <Input default_value="world" error=true size="lg" />
+                </>
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        let (controlled, uncontrolled) = rendered.split_once("</div>").unwrap();
+        assert!(
+            controlled.contains(r#"data-variant="outlined""#),
+            "{controlled}"
+        );
+        assert!(
+            controlled.contains(r#"data-color="neutral""#),
+            "{controlled}"
+        );
+        assert!(controlled.contains(r#"value="hello""#), "{controlled}");
+        assert!(controlled.contains(r#"placeholder="Name""#), "{controlled}");
+        assert!(
+            controlled.contains(r#"<span class="input-start-decorator">@</span>"#),
+            "{controlled}"
+        );
+        assert!(uncontrolled.contains(r#"value="world""#), "{uncontrolled}");
+        assert!(
+            uncontrolled.contains(r#"data-color="fatal""#),
+            "{uncontrolled}"
+        );
+        assert!(
+            uncontrolled.contains(r#"aria-invalid="true""#),
+            "{uncontrolled}"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! A multi line text input
+
+use web_sys::HtmlTextAreaElement;
+use yew::{
+    classes, function_component, html, AttrValue, Callback, Html, InputEvent, Properties,
+    TargetCast,
+};
+
+use textiler_core::prelude::*;
+use textiler_core::system::{ColorProp, SizeProp, VariantProp};
+
+use crate::inputs::form_control::use_form_control;
+use crate::inputs::input::{input_state, input_sx, use_controlled_value, InputStateProps};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct TextareaProps {
+    #[prop_or_default]
+    pub sx: Sx,
+    /// Defaults to outlined
+    #[prop_or_default]
+    pub variant: VariantProp,
+    /// Defaults to neutral, or fatal if the textarea has an error
+    #[prop_or_default]
+    pub color: ColorProp,
+    /// Defaults to the size of the surrounding [`FormControl`](crate::inputs::FormControl)
+    #[prop_or_default]
+    pub size: SizeProp,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub error: bool,
+    #[prop_or_default]
+    pub required: bool,
+    /// Defaults to the id of the surrounding [`FormControl`](crate::inputs::FormControl)
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    #[prop_or_default]
+    pub name: Option<AttrValue>,
+    #[prop_or_default]
+    pub placeholder: Option<AttrValue>,
+    /// Makes the textarea controlled, always showing this value
+    #[prop_or_default]
+    pub value: Option<AttrValue>,
+    /// The initial value of an uncontrolled textarea
+    #[prop_or_default]
+    pub default_value: Option<AttrValue>,
+    /// Called with the new value whenever it's edited
+    #[prop_or_default]
+    pub on_change: Option<Callback<String>>,
+    /// The fewest rows shown
+    #[prop_or(1)]
+    pub min_rows: u32,
+    /// The most rows shown before the textarea scrolls. Unlimited if not set.
+    #[prop_or_default]
+    pub max_rows: Option<u32>,
+    /// Shown above the textarea
+    #[prop_or_default]
+    pub start_decorator: Option<Html>,
+    /// Shown below the textarea
+    #[prop_or_default]
+    pub end_decorator: Option<Html>,
+    /// Takes the full width of its container
+    #[prop_or_default]
+    pub full_width: bool,
+}
+
+#[function_component]
+pub fn Textarea(props: &TextareaProps) -> Html {
+    let control = use_form_control();
+    let state = input_state(
+        &control,
+        InputStateProps {
+            variant: props.variant,
+            color: props.color.clone(),
+            size: props.size,
+            disabled: props.disabled,
+            error: props.error,
+            required: props.required,
+            id: props.id.clone(),
+        },
+    );
+    let (value, set_value) = use_controlled_value(
+        props.value.clone(),
+        props.default_value.clone(),
+        props.on_change.clone(),
+    );
+    let (size, variant) = (state.size, state.variant);
+    let full_width = props.full_width;
+    let class_sx = use_sx(move |_, _| {
+        let mut sx = input_sx(size, variant).extend(sx! {
+            "flexDirection": "column",
+            "alignItems": "stretch",
+            "paddingBlock": "0.375rem",
+            "& > .input-input": {
+                "overflow": "auto",
+            },
+        });
+        if full_width {
+            sx.insert("width", "100%");
+        }
+        sx
+    });
+    let oninput = Callback::from(move |e: InputEvent| {
+        set_value.emit(e.target_unchecked_into::<HtmlTextAreaElement>().value())
+    });
+    let rows = autosize_rows(&value, props.min_rows, props.max_rows);
+
+    html! {
+        <StylingBox
+            class={classes!("textarea", class_sx)}
+            sx={props.sx.clone()}
+            {variant}
+            color={state.color.clone()}
+            disabled={state.disabled}
+        >
+            if let Some(decorator) = props.start_decorator.clone() {
+                <div class="input-start-decorator">{decorator}</div>
+            }
+            <textarea
+                class="input-input"
+                id={state.id.clone()}
+                name={props.name.clone()}
+                placeholder={props.placeholder.clone()}
+                rows={rows.to_string()}
+                value={value}
+                disabled={state.disabled}
+                required={state.required}
+                aria-invalid={state.error.then_some("true")}
+                aria-describedby={state.described_by.clone()}
+                {oninput}
+            />
+            if let Some(decorator) = props.end_decorator.clone() {
+                <div class="input-end-decorator">{decorator}</div>
+            }
+        </StylingBox>
+    }
+}
+
+/// Gets the number of rows to show for a value, growing with its lines between `min_rows` and
+/// `max_rows`. Lines wrapped by the browser aren't counted.
+fn autosize_rows(value: &str, min_rows: u32, max_rows: Option<u32>) -> u32 {
+    let lines = value.split('\n').count() as u32;
+    let rows = lines.max(min_rows);
+    match max_rows {
+        Some(max_rows) => rows.min(max_rows.max(min_rows)),
+        None => rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use textiler_core::context::ThemeProvider;
+    use textiler_core::style_manager::StyleCollector;
+    use yew::{function_component, html, Html, ServerRenderer};
+
+    use super::*;
+
+    #[test]
+    fn autosizes_between_min_and_max_rows() {
+        assert_eq!(autosize_rows("", 2, Some(4)), 2);
+        assert_eq!(autosize_rows("a\nb\nc", 2, Some(4)), 3);
+        assert_eq!(autosize_rows("a\nb\nc\nd\ne\nf", 2, Some(4)), 4);
+        assert_eq!(autosize_rows("a\nb\nc\nd\ne\nf", 2, None), 6);
+    }
+
+    #[tokio::test]
+    async fn render_textarea() {
+        #[function_component]
+        fn Test() -> Html {
+            html! {
+                <Textarea default_value="one\ntwo" min_rows=1 max_rows=5 />
+            }
+        }
+
+        let rendered = ServerRenderer::<Test>::new().render().await;
+        assert!(rendered.contains("<textarea"), "{rendered}");
+        assert!(rendered.contains(r#"rows="2""#), "{rendered}");
+        assert!(
+            rendered.contains(r#"data-variant="outlined""#),
+            "{rendered}"
+        );
+    }
+
+    #[tokio::test]
+    async fn textarea_overrides_input_styling() {
+        #[derive(PartialEq, Properties)]
+        struct TestProps {
+            collector: StyleCollector,
+        }
+
+        #[function_component]
+        fn Test(props: &TestProps) -> Html {
+            html! {
+                <ThemeProvider collector={props.collector.clone()}>
+                    <Textarea />
+                </ThemeProvider>
+            }
+        }
+
+        let collector = StyleCollector::new();
+        let _ = ServerRenderer::<Test>::with_props({
+            let collector = collector.clone();
+            move || TestProps { collector }
+        })
+        .render()
+        .await;
+
+        let css = collector.css();
+        assert!(css.contains("align-items: stretch;"), "{css}");
+        assert!(css.contains("padding-block: 0.375rem;"), "{css}");
+        assert!(!css.contains("align-items: center;"), "{css}");
+    }
+}
@@ -3,6 +3,7 @@
 //!
 //!
 
+pub mod inputs;
 pub mod surfaces;
 pub mod system;
 pub mod typography;
@@ -40,7 +40,7 @@ pub struct CardProps {
 pub fn Card(CardProps { direction, sx, variant, color, children, onclick }: &CardProps) -> Html {
     let class_sx = use_sx(|theme, mode| {
         sx! {
-            borderRadius: "8px",
+            borderRadius: "radius.md",
             p: "3px",
             display: "flex",
             width: "fit-content"
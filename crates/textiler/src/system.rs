@@ -1,4 +1,4 @@
 //! System components exposed for easier downstream consumption
 
-pub use textiler_core::system::{StylingBox, ColorProp, VariantProp};
+pub use textiler_core::system::{StylingBox, ColorProp, SizeProp, VariantProp};
 